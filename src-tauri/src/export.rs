@@ -0,0 +1,93 @@
+use chrono::{NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::timer::{SessionRecord, TimerError};
+
+pub mod csv;
+pub mod ics;
+pub mod jsonl;
+
+/// File format for exported history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// One row per session, for spreadsheets
+    Csv,
+    /// One JSON record per line, for scripts
+    Jsonl,
+    /// One calendar event per work session
+    Ics,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+/// Inclusive range of local dates to export, and the format to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub format: ExportFormat,
+}
+
+impl ExportQuery {
+    /// Name of the exported file, e.g. `pomodoro-history-2026-01-01-2026-01-31.csv`
+    pub fn file_name(&self) -> String {
+        format!(
+            "pomodoro-history-{}-{}.{}",
+            self.from,
+            self.to,
+            self.format.extension()
+        )
+    }
+}
+
+/// Render the sessions that started within the query's range, splitting days
+/// in `tz`.
+pub fn render<Tz: TimeZone>(
+    records: &[SessionRecord],
+    query: &ExportQuery,
+    tz: &Tz,
+) -> Result<String, TimerError> {
+    if query.from > query.to {
+        return Err(TimerError::InvalidDateRange {
+            from: query.from,
+            to: query.to,
+        });
+    }
+
+    let selected: Vec<SessionRecord> = records
+        .iter()
+        .filter(|record| {
+            let date = record.started_at.with_timezone(tz).date_naive();
+            (query.from..=query.to).contains(&date)
+        })
+        .cloned()
+        .collect();
+
+    match query.format {
+        ExportFormat::Csv => csv::to_csv(&selected),
+        ExportFormat::Jsonl => jsonl::to_jsonl(&selected),
+        ExportFormat::Ics => Ok(ics::to_ics(&selected)),
+    }
+    .map_err(|reason| TimerError::ExportFailed { reason })
+}
+
+// Serialized name of a unit enum variant such as `Phase::LongBreak`
+fn variant_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,87 @@
+use serde::Serialize;
+
+use super::variant_name;
+use crate::timer::{InterruptionKind, SessionRecord};
+
+const HEADER: [&str; 16] = [
+    "phase",
+    "outcome",
+    "started_at",
+    "ended_at",
+    "planned_secs",
+    "focused_secs",
+    "pause_count",
+    "paused_secs",
+    "overtime_secs",
+    "task",
+    "project",
+    "internal_interruptions",
+    "external_interruptions",
+    "interruptions",
+    "adjustments",
+    "suspensions",
+];
+
+/// RFC 4180 CSV with a header row and one row per session. A missing task or
+/// project is an empty field. Besides the interruption counts, the full lists
+/// of interruptions, adjustments and suspensions are kept as JSON arrays for scripts.
+pub fn to_csv(records: &[SessionRecord]) -> Result<String, String> {
+    let mut out = String::new();
+    write_row(&mut out, HEADER.iter().map(|name| name.to_string()));
+    for record in records {
+        write_row(
+            &mut out,
+            [
+                variant_name(&record.phase),
+                variant_name(&record.outcome),
+                record.started_at.to_rfc3339(),
+                record.ended_at.to_rfc3339(),
+                record.planned_secs.to_string(),
+                record.focused_secs.to_string(),
+                record.pause_count.to_string(),
+                record.paused_secs.to_string(),
+                record.overtime_secs.to_string(),
+                record.tag.task.clone().unwrap_or_default(),
+                record.tag.project.clone().unwrap_or_default(),
+                record
+                    .interruption_count(InterruptionKind::Internal)
+                    .to_string(),
+                record
+                    .interruption_count(InterruptionKind::External)
+                    .to_string(),
+                json_list(&record.interruptions)?,
+                json_list(&record.adjustments)?,
+                json_list(&record.suspensions)?,
+            ],
+        );
+    }
+    Ok(out)
+}
+
+// Empty for an empty list, to keep the column readable
+fn json_list<T: Serialize>(items: &[T]) -> Result<String, String> {
+    if items.is_empty() {
+        return Ok(String::new());
+    }
+    serde_json::to_string(items).map_err(|e| e.to_string())
+}
+
+fn write_row(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        // Quote fields that would otherwise break the row apart
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&field);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests;
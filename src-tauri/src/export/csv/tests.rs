@@ -0,0 +1,191 @@
+//! Unit tests for the CSV exporter
+
+use super::*;
+use crate::export::tests::{parse_variant, sample_records};
+use crate::timer::TaskTag;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+
+// Reader for the exported rows, to check that no field is lost
+fn from_csv(text: &str) -> Result<Vec<SessionRecord>, String> {
+    let mut rows = parse_rows(text)?.into_iter();
+    match rows.next() {
+        Some(header) if header == HEADER => {}
+        _ => return Err("Missing or unexpected CSV header".to_string()),
+    }
+
+    rows.enumerate()
+        .map(|(index, row)| parse_record(&row).map_err(|e| format!("Row {}: {}", index + 2, e)))
+        .collect()
+}
+
+fn parse_record(row: &[String]) -> Result<SessionRecord, String> {
+    let [
+        phase,
+        outcome,
+        started_at,
+        ended_at,
+        planned_secs,
+        focused_secs,
+        pause_count,
+        paused_secs,
+        overtime_secs,
+        task,
+        project,
+        _internal_interruptions,
+        _external_interruptions,
+        interruptions,
+        adjustments,
+        suspensions,
+    ] = row
+    else {
+        return Err(format!(
+            "Expected {} columns, found {}",
+            HEADER.len(),
+            row.len()
+        ));
+    };
+
+    Ok(SessionRecord {
+        phase: parse_variant(phase)?,
+        outcome: parse_variant(outcome)?,
+        started_at: parse_time(started_at)?,
+        ended_at: parse_time(ended_at)?,
+        planned_secs: parse_number(planned_secs)?,
+        focused_secs: parse_number(focused_secs)?,
+        pause_count: parse_number(pause_count)?,
+        paused_secs: parse_number(paused_secs)?,
+        overtime_secs: parse_number(overtime_secs)?,
+        tag: TaskTag {
+            task: (!task.is_empty()).then(|| task.clone()),
+            project: (!project.is_empty()).then(|| project.clone()),
+        },
+        interruptions: parse_list(interruptions)?,
+        adjustments: parse_list(adjustments)?,
+        suspensions: parse_list(suspensions)?,
+    })
+}
+
+fn parse_list<T: DeserializeOwned>(field: &str) -> Result<Vec<T>, String> {
+    if field.is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(field).map_err(|e| e.to_string())
+}
+
+fn parse_time(field: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(field)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid time '{}': {}", field, e))
+}
+
+fn parse_number(field: &str) -> Result<u32, String> {
+    field
+        .parse()
+        .map_err(|e| format!("Invalid number '{}': {}", field, e))
+}
+
+// Split CSV text into rows of unquoted fields
+fn parse_rows(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => in_quotes = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("Unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[test]
+fn test_csv_round_trips_records() {
+    let records = sample_records();
+
+    let text = to_csv(&records).unwrap();
+
+    assert_eq!(from_csv(&text).unwrap(), records);
+}
+
+#[test]
+fn test_csv_starts_with_header_row() {
+    let text = to_csv(&sample_records()[..1]).unwrap();
+
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next(),
+        Some(
+            "phase,outcome,started_at,ended_at,planned_secs,focused_secs,pause_count,paused_secs,overtime_secs,task,project,internal_interruptions,external_interruptions,interruptions,adjustments,suspensions"
+        )
+    );
+    assert_eq!(
+        lines.next(),
+        Some(concat!(
+            r#"work,completed,2026-01-05T09:00:00+00:00,2026-01-05T09:27:00+00:00,"#,
+            r#"1500,1500,1,120,45,"Write report, draft ""v2""",Acme; Q1,1,1,"#,
+            r#""[{""kind"":""external"",""at"":""2026-01-05T09:10:00Z"","#,
+            r#"""note"":""Call from Bob; urgent, sort of""},"#,
+            r#"{""kind"":""internal"",""at"":""2026-01-05T09:20:00Z""}]","#,
+            r#""[{""at"":""2026-01-05T09:24:00Z"",""deltaSecs"":120}]","#
+        ))
+    );
+}
+
+#[test]
+fn test_csv_quotes_fields_with_separators() {
+    let mut out = String::new();
+    write_row(
+        &mut out,
+        [
+            "plain".to_string(),
+            "a,b".to_string(),
+            "say \"hi\"".to_string(),
+        ],
+    );
+
+    assert_eq!(out, "plain,\"a,b\",\"say \"\"hi\"\"\"\r\n");
+    assert_eq!(
+        parse_rows(&out).unwrap(),
+        vec![vec!["plain", "a,b", "say \"hi\""]]
+    );
+}
+
+#[test]
+fn test_csv_rejects_unknown_header() {
+    assert!(from_csv("started,ended\r\n").is_err());
+}
+
+#[test]
+fn test_csv_reports_row_with_missing_columns() {
+    let mut text = to_csv(&[]).unwrap();
+    text.push_str("work,completed\r\n");
+
+    assert_eq!(
+        from_csv(&text).unwrap_err(),
+        "Row 2: Expected 16 columns, found 2"
+    );
+}
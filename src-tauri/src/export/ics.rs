@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+
+use super::variant_name;
+use crate::timer::{Adjustment, Interruption, Phase, SessionRecord, Suspension};
+
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const SUMMARY: &str = "Focus session";
+// Longest content line in octets; longer lines are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// iCalendar (RFC 5545) file with one VEVENT per work session, so focus
+/// blocks show up in calendars. Breaks are left out.
+///
+/// Calendar times have whole-second precision; the remaining record fields
+/// are kept in `X-POMODORO-*` properties, with one `X-POMODORO-INTERRUPTION`
+/// per interruption, one `X-POMODORO-ADJUSTMENT` per adjustment and one
+/// `X-POMODORO-SUSPENSION` per suspension.
+pub fn to_ics(records: &[SessionRecord]) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//pomodoro-timer//Session history//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+
+    for record in records.iter().filter(|record| record.phase == Phase::Work) {
+        let start = format_time(record.started_at);
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}-work@pomodoro-timer", start));
+        push_line(
+            &mut out,
+            &format!("DTSTAMP:{}", format_time(record.ended_at)),
+        );
+        push_line(&mut out, &format!("DTSTART:{}", start));
+        push_line(&mut out, &format!("DTEND:{}", format_time(record.ended_at)));
+        push_line(
+            &mut out,
+            &format!("SUMMARY:{}", escape_text(&summary(record))),
+        );
+        if let Some(task) = &record.tag.task {
+            push_line(&mut out, &format!("X-POMODORO-TASK:{}", escape_text(task)));
+        }
+        if let Some(project) = &record.tag.project {
+            push_line(
+                &mut out,
+                &format!("X-POMODORO-PROJECT:{}", escape_text(project)),
+            );
+        }
+        push_line(
+            &mut out,
+            &format!("X-POMODORO-OUTCOME:{}", variant_name(&record.outcome)),
+        );
+        push_line(
+            &mut out,
+            &format!("X-POMODORO-PLANNED-SECS:{}", record.planned_secs),
+        );
+        push_line(
+            &mut out,
+            &format!("X-POMODORO-FOCUSED-SECS:{}", record.focused_secs),
+        );
+        push_line(
+            &mut out,
+            &format!("X-POMODORO-PAUSE-COUNT:{}", record.pause_count),
+        );
+        push_line(
+            &mut out,
+            &format!("X-POMODORO-PAUSED-SECS:{}", record.paused_secs),
+        );
+        push_line(
+            &mut out,
+            &format!("X-POMODORO-OVERTIME-SECS:{}", record.overtime_secs),
+        );
+        for interruption in &record.interruptions {
+            push_line(&mut out, &interruption_line(interruption));
+        }
+        for adjustment in &record.adjustments {
+            push_line(&mut out, &adjustment_line(adjustment));
+        }
+        for suspension in &record.suspensions {
+            push_line(&mut out, &suspension_line(suspension));
+        }
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format(TIME_FORMAT).to_string()
+}
+
+// Kind and time go in parameters so the value is just the note
+fn interruption_line(interruption: &Interruption) -> String {
+    format!(
+        "X-POMODORO-INTERRUPTION;KIND={};AT={}:{}",
+        variant_name(&interruption.kind),
+        format_time(interruption.at),
+        escape_text(interruption.note.as_deref().unwrap_or_default())
+    )
+}
+
+// Signed change in seconds as the value, e.g. "-120"
+fn adjustment_line(adjustment: &Adjustment) -> String {
+    format!(
+        "X-POMODORO-ADJUSTMENT;AT={}:{}",
+        format_time(adjustment.at),
+        adjustment.delta_secs
+    )
+}
+
+// Seconds asleep as the value, e.g. "3600"
+fn suspension_line(suspension: &Suspension) -> String {
+    format!(
+        "X-POMODORO-SUSPENSION;AT={};COUNTED={}:{}",
+        format_time(suspension.at),
+        suspension.counted,
+        suspension.secs
+    )
+}
+
+// Event title, e.g. "Focus session: Write report (Acme)"
+fn summary(record: &SessionRecord) -> String {
+    let mut summary = SUMMARY.to_string();
+    if let Some(task) = &record.tag.task {
+        summary.push_str(&format!(": {}", task));
+    }
+    if let Some(project) = &record.tag.project {
+        summary.push_str(&format!(" ({})", project));
+    }
+    summary
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+// Write a content line, folding it onto continuation lines that start with a
+// space so no line exceeds the octet limit
+fn push_line(out: &mut String, line: &str) {
+    let mut limit = MAX_LINE_OCTETS;
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > limit {
+            out.push_str("\r\n ");
+            // The leading space counts towards the limit
+            limit = MAX_LINE_OCTETS - 1;
+            octets = 0;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,240 @@
+//! Unit tests for the iCalendar exporter
+
+use super::*;
+use crate::export::tests::{parse_variant, sample_records};
+use crate::timer::{Adjustment, Interruption, Suspension, TaskTag};
+use chrono::NaiveDateTime;
+use std::collections::HashMap;
+
+// Lines of a VEVENT collected so far
+#[derive(Default)]
+struct EventLines {
+    properties: HashMap<String, String>,
+    interruptions: Vec<Interruption>,
+    adjustments: Vec<Adjustment>,
+    suspensions: Vec<Suspension>,
+}
+
+// Reader for the exported events, to check that no field is lost
+fn from_ics(text: &str) -> Result<Vec<SessionRecord>, String> {
+    let mut records = Vec::new();
+    let mut event: Option<EventLines> = None;
+
+    for line in unfold(text) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut parts = name.split(';');
+        let name = parts.next().unwrap_or(name);
+        let params: HashMap<&str, &str> = parts.filter_map(|part| part.split_once('=')).collect();
+        match (name, value, event.as_mut()) {
+            ("BEGIN", "VEVENT", _) => event = Some(EventLines::default()),
+            ("END", "VEVENT", _) => {
+                let lines = event.take().ok_or("END:VEVENT without BEGIN")?;
+                records.push(parse_event(lines)?);
+            }
+            ("X-POMODORO-INTERRUPTION", _, Some(lines)) => {
+                lines.interruptions.push(Interruption {
+                    kind: parse_variant(params.get("KIND").ok_or("Interruption without KIND")?)?,
+                    at: parse_time(params.get("AT").ok_or("Interruption without AT")?)?,
+                    note: (!value.is_empty()).then(|| unescape_text(value)),
+                });
+            }
+            ("X-POMODORO-ADJUSTMENT", _, Some(lines)) => {
+                lines.adjustments.push(Adjustment {
+                    at: parse_time(params.get("AT").ok_or("Adjustment without AT")?)?,
+                    delta_secs: value
+                        .parse()
+                        .map_err(|e| format!("Invalid adjustment '{}': {}", value, e))?,
+                });
+            }
+            ("X-POMODORO-SUSPENSION", _, Some(lines)) => {
+                let counted = params.get("COUNTED").ok_or("Suspension without COUNTED")?;
+                lines.suspensions.push(Suspension {
+                    at: parse_time(params.get("AT").ok_or("Suspension without AT")?)?,
+                    secs: value
+                        .parse()
+                        .map_err(|e| format!("Invalid suspension '{}': {}", value, e))?,
+                    counted: counted
+                        .parse()
+                        .map_err(|e| format!("Invalid COUNTED '{}': {}", counted, e))?,
+                });
+            }
+            (_, _, Some(lines)) => {
+                lines.properties.insert(name.to_string(), value.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+fn parse_event(lines: EventLines) -> Result<SessionRecord, String> {
+    let properties = &lines.properties;
+    let get = |name: &str| {
+        properties
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| format!("Event is missing {}", name))
+    };
+    let number = |name: &str| -> Result<u32, String> {
+        get(name)?
+            .parse()
+            .map_err(|e| format!("Invalid {}: {}", name, e))
+    };
+
+    Ok(SessionRecord {
+        phase: Phase::Work,
+        outcome: parse_variant(get("X-POMODORO-OUTCOME")?)?,
+        started_at: parse_time(get("DTSTART")?)?,
+        ended_at: parse_time(get("DTEND")?)?,
+        planned_secs: number("X-POMODORO-PLANNED-SECS")?,
+        focused_secs: number("X-POMODORO-FOCUSED-SECS")?,
+        pause_count: number("X-POMODORO-PAUSE-COUNT")?,
+        paused_secs: number("X-POMODORO-PAUSED-SECS")?,
+        overtime_secs: number("X-POMODORO-OVERTIME-SECS")?,
+        tag: TaskTag {
+            task: properties
+                .get("X-POMODORO-TASK")
+                .map(|text| unescape_text(text)),
+            project: properties
+                .get("X-POMODORO-PROJECT")
+                .map(|text| unescape_text(text)),
+        },
+        interruptions: lines.interruptions,
+        adjustments: lines.adjustments,
+        suspensions: lines.suspensions,
+    })
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    NaiveDateTime::parse_from_str(value, TIME_FORMAT)
+        .map(|time| time.and_utc())
+        .map_err(|e| format!("Invalid time '{}': {}", value, e))
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(escaped) => out.push(escaped),
+            None => {}
+        }
+    }
+    out
+}
+
+// Join continuation lines, which start with a space or tab
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+#[test]
+fn test_ics_round_trips_work_sessions() {
+    let records = sample_records();
+    let work: Vec<SessionRecord> = records
+        .iter()
+        .filter(|record| record.phase == Phase::Work)
+        .cloned()
+        .collect();
+
+    let text = to_ics(&records);
+
+    assert_eq!(text.matches("BEGIN:VEVENT").count(), 2);
+    assert_eq!(from_ics(&text).unwrap(), work);
+}
+
+#[test]
+fn test_ics_event_has_calendar_times() {
+    let text = to_ics(&sample_records()[..1]);
+
+    assert!(text.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(text.ends_with("END:VCALENDAR\r\n"));
+    assert!(text.contains("\r\nUID:20260105T090000Z-work@pomodoro-timer\r\n"));
+    assert!(text.contains("\r\nDTSTART:20260105T090000Z\r\n"));
+    assert!(text.contains("\r\nDTEND:20260105T092700Z\r\n"));
+    assert!(
+        text.contains("\r\nSUMMARY:Focus session: Write report\\, draft \"v2\" (Acme\\; Q1)\r\n")
+    );
+    assert!(text.contains("\r\nX-POMODORO-PROJECT:Acme\\; Q1\r\n"));
+}
+
+#[test]
+fn test_ics_reads_folded_lines() {
+    let text = to_ics(&sample_records()[..1]).replace(
+        "X-POMODORO-OUTCOME:completed",
+        "X-POMODORO-OUTCOME:comp\r\n lete\r\n\td",
+    );
+
+    assert_eq!(from_ics(&text).unwrap(), sample_records()[..1].to_vec());
+}
+
+#[test]
+fn test_ics_reports_missing_property() {
+    let text = to_ics(&sample_records()[..1]).replace("X-POMODORO-PAUSE-COUNT:1\r\n", "");
+
+    assert_eq!(
+        from_ics(&text).unwrap_err(),
+        "Event is missing X-POMODORO-PAUSE-COUNT"
+    );
+}
+
+#[test]
+fn test_ics_folds_long_lines() {
+    let mut record = sample_records()[0].clone();
+    record.tag.task = Some("Ünïcödé ".repeat(20));
+
+    let text = to_ics(&[record.clone()]);
+
+    assert!(text.split("\r\n").all(|line| line.len() <= 75));
+    assert_eq!(from_ics(&text).unwrap(), vec![record]);
+}
+
+#[test]
+fn test_ics_writes_one_property_per_interruption() {
+    let lines = unfold(&to_ics(&sample_records()[..1]));
+
+    let interruptions: Vec<&str> = lines
+        .iter()
+        .map(String::as_str)
+        .filter(|line| line.starts_with("X-POMODORO-INTERRUPTION"))
+        .collect();
+    assert_eq!(
+        interruptions,
+        vec![
+            "X-POMODORO-INTERRUPTION;KIND=external;AT=20260105T091000Z:Call from Bob\\; urgent\\, sort of",
+            "X-POMODORO-INTERRUPTION;KIND=internal;AT=20260105T092000Z:",
+        ]
+    );
+}
+
+#[test]
+fn test_ics_writes_signed_adjustments() {
+    let mut record = sample_records()[0].clone();
+    record.adjustments[0].delta_secs = -90;
+
+    let text = to_ics(&[record]);
+
+    assert!(text.contains("\r\nX-POMODORO-ADJUSTMENT;AT=20260105T092400Z:-90\r\n"));
+}
+
+#[test]
+fn test_ics_writes_one_property_per_suspension() {
+    let text = to_ics(&sample_records());
+
+    assert!(text.contains("\r\nX-POMODORO-SUSPENSION;AT=20260105T100500Z;COUNTED=false:3600\r\n"));
+}
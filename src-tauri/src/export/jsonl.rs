@@ -0,0 +1,14 @@
+use crate::timer::SessionRecord;
+
+/// One `SessionRecord` per line, in the same shape as the history file.
+pub fn to_jsonl(records: &[SessionRecord]) -> Result<String, String> {
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests;
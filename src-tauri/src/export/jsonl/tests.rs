@@ -0,0 +1,39 @@
+//! Unit tests for the JSON Lines exporter
+
+use super::*;
+use crate::export::tests::sample_records;
+
+// Reader for the exported lines, to check that no field is lost
+fn from_jsonl(text: &str) -> Result<Vec<SessionRecord>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| format!("Line {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+#[test]
+fn test_jsonl_round_trips_records() {
+    let records = sample_records();
+
+    let text = to_jsonl(&records).unwrap();
+
+    assert_eq!(text.lines().count(), records.len());
+    assert_eq!(from_jsonl(&text).unwrap(), records);
+}
+
+#[test]
+fn test_jsonl_uses_history_field_names() {
+    let text = to_jsonl(&sample_records()[..1]).unwrap();
+
+    assert!(text.starts_with(r#"{"phase":"work","outcome":"completed","startedAt":"#));
+}
+
+#[test]
+fn test_jsonl_reports_invalid_line() {
+    let error = from_jsonl("\n{\"phase\":").unwrap_err();
+
+    assert!(error.starts_with("Line 2:"));
+}
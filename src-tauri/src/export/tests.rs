@@ -0,0 +1,148 @@
+//! Unit tests for history export
+
+use super::*;
+use crate::timer::{
+    Adjustment, Interruption, InterruptionKind, Phase, SessionOutcome, Suspension, TaskTag,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::de::DeserializeOwned;
+
+fn time(text: &str) -> DateTime<Utc> {
+    text.parse().unwrap()
+}
+
+/// A few sessions covering every phase and outcome, shared by the exporter
+/// round-trip tests
+pub(super) fn sample_records() -> Vec<SessionRecord> {
+    vec![
+        SessionRecord {
+            ended_at: time("2026-01-05T09:27:00Z"),
+            focused_secs: 1500,
+            pause_count: 1,
+            paused_secs: 120,
+            overtime_secs: 45,
+            tag: TaskTag {
+                task: Some("Write report, draft \"v2\"".to_string()),
+                project: Some("Acme; Q1".to_string()),
+            },
+            interruptions: vec![
+                Interruption {
+                    kind: InterruptionKind::External,
+                    at: time("2026-01-05T09:10:00Z"),
+                    note: Some("Call from Bob; urgent, sort of".to_string()),
+                },
+                Interruption {
+                    kind: InterruptionKind::Internal,
+                    at: time("2026-01-05T09:20:00Z"),
+                    note: None,
+                },
+            ],
+            adjustments: vec![Adjustment {
+                at: time("2026-01-05T09:24:00Z"),
+                delta_secs: 120,
+            }],
+            ..SessionRecord::sample(
+                Phase::Work,
+                SessionOutcome::Completed,
+                time("2026-01-05T09:00:00Z"),
+                1500,
+            )
+        },
+        SessionRecord::sample(
+            Phase::Break,
+            SessionOutcome::Completed,
+            time("2026-01-05T09:28:00Z"),
+            300,
+        ),
+        SessionRecord {
+            ended_at: time("2026-01-05T10:12:30Z"),
+            focused_secs: 750,
+            tag: TaskTag {
+                task: None,
+                project: Some("Acme; Q1".to_string()),
+            },
+            suspensions: vec![Suspension {
+                at: time("2026-01-05T10:05:00Z"),
+                secs: 3600,
+                counted: false,
+            }],
+            ..SessionRecord::sample(
+                Phase::Work,
+                SessionOutcome::SwitchedAway,
+                time("2026-01-05T10:00:00Z"),
+                1500,
+            )
+        },
+        SessionRecord {
+            ended_at: time("2026-01-06T16:05:00Z"),
+            focused_secs: 300,
+            pause_count: 2,
+            paused_secs: 30,
+            ..SessionRecord::sample(
+                Phase::LongBreak,
+                SessionOutcome::Cleared,
+                time("2026-01-06T16:00:00Z"),
+                900,
+            )
+        },
+    ]
+}
+
+/// Inverse of `variant_name`, for the exporter round-trip tests
+pub(super) fn parse_variant<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|e| format!("Unknown value '{}': {}", name, e))
+}
+
+fn query(from: &str, to: &str, format: ExportFormat) -> ExportQuery {
+    ExportQuery {
+        from: from.parse().unwrap(),
+        to: to.parse().unwrap(),
+        format,
+    }
+}
+
+#[test]
+fn test_render_only_includes_sessions_in_range() {
+    let text = render(
+        &sample_records(),
+        &query("2026-01-06", "2026-01-06", ExportFormat::Jsonl),
+        &Utc,
+    )
+    .unwrap();
+
+    assert_eq!(text, jsonl::to_jsonl(&sample_records()[3..]).unwrap());
+}
+
+#[test]
+fn test_render_splits_days_in_local_time() {
+    // 16:00 UTC on the 6th is already the 7th nine hours east
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    let text = render(
+        &sample_records(),
+        &query("2026-01-06", "2026-01-06", ExportFormat::Jsonl),
+        &tokyo,
+    )
+    .unwrap();
+
+    assert_eq!(text, "");
+}
+
+#[test]
+fn test_render_rejects_reversed_range() {
+    let result = render(
+        &sample_records(),
+        &query("2026-01-06", "2026-01-05", ExportFormat::Csv),
+        &Utc,
+    );
+
+    assert!(matches!(result, Err(TimerError::InvalidDateRange { .. })));
+}
+
+#[test]
+fn test_file_name_includes_range_and_extension() {
+    assert_eq!(
+        query("2026-01-01", "2026-01-31", ExportFormat::Ics).file_name(),
+        "pomodoro-history-2026-01-01-2026-01-31.ics"
+    );
+}
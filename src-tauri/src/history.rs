@@ -0,0 +1,93 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+use crate::timer::SessionRecord;
+
+/// JSON Lines file in the app data directory holding every finished session,
+/// oldest first. Sessions are appended, and only removed again by an undo.
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Read all records. A missing file is an empty history, and a line that
+    /// does not parse (e.g. cut short by a crash) is skipped.
+    pub fn load(&self) -> Result<Vec<SessionRecord>, String> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let mut records = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(record) => records.push(record),
+                Err(e) => log::warn!(
+                    "Skipping invalid history line {} in {}: {}",
+                    index + 1,
+                    self.path.display(),
+                    e
+                ),
+            }
+        }
+        Ok(records)
+    }
+
+    pub fn append(&self, records: &[SessionRecord]) -> Result<(), String> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+
+        // Write all lines at once so a batch is never interleaved
+        let mut lines = String::new();
+        for record in records {
+            lines.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+            lines.push('\n');
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| e.to_string())?;
+        file.write_all(lines.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Remove the latest record equal to each of `records`, e.g. sessions
+    /// withdrawn by an undo. Records not in the file are ignored.
+    pub fn remove(&self, records: &[SessionRecord]) -> Result<(), String> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut kept = self.load()?;
+        for record in records {
+            if let Some(index) = kept.iter().rposition(|r| r == record) {
+                kept.remove(index);
+            }
+        }
+
+        let mut lines = String::new();
+        for record in &kept {
+            lines.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+            lines.push('\n');
+        }
+        // Replace the file in one step so a crash never loses the history
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp_path, lines).map_err(|e| e.to_string())?;
+        fs::rename(&tmp_path, &self.path).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+//! Unit tests for the session history file
+
+use super::*;
+use crate::test_support::TempDir;
+use crate::timer::{Phase, SessionOutcome};
+
+// Helper: history file in a fresh temporary directory
+fn temp_history(name: &str) -> (HistoryStore, TempDir) {
+    let dir = TempDir::new(name);
+    (HistoryStore::new(dir.join("history.jsonl")), dir)
+}
+
+fn record(outcome: SessionOutcome, started_at: &str) -> SessionRecord {
+    SessionRecord::sample(Phase::Work, outcome, started_at.parse().unwrap(), 1500)
+}
+
+#[test]
+fn test_load_missing_file_returns_empty_history() {
+    let (history, _dir) = temp_history("history-missing");
+
+    assert_eq!(history.load(), Ok(Vec::new()));
+}
+
+#[test]
+fn test_append_keeps_earlier_records() {
+    let (history, _dir) = temp_history("history-append");
+    let first = record(SessionOutcome::Completed, "2026-01-05T09:00:00Z");
+    let second = record(SessionOutcome::Cleared, "2026-01-05T10:00:00Z");
+    let third = record(SessionOutcome::SwitchedAway, "2026-01-05T11:00:00Z");
+
+    history.append(std::slice::from_ref(&first)).unwrap();
+    history.append(&[second.clone(), third.clone()]).unwrap();
+
+    assert_eq!(history.load(), Ok(vec![first, second, third]));
+}
+
+#[test]
+fn test_append_nothing_creates_no_file() {
+    let (history, dir) = temp_history("history-empty-append");
+
+    history.append(&[]).unwrap();
+
+    assert!(!dir.path().exists());
+}
+
+#[test]
+fn test_load_skips_truncated_line() {
+    let (history, dir) = temp_history("history-truncated");
+    let first = record(SessionOutcome::Completed, "2026-01-05T09:00:00Z");
+    history.append(std::slice::from_ref(&first)).unwrap();
+
+    // Simulate a crash in the middle of writing the next line
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(dir.join("history.jsonl"))
+        .unwrap();
+    file.write_all(b"{\"phase\":\"work\",\"outc").unwrap();
+
+    assert_eq!(history.load(), Ok(vec![first]));
+}
+
+#[test]
+fn test_remove_drops_latest_matching_records() {
+    let (history, _dir) = temp_history("history-remove");
+    let first = record(SessionOutcome::Completed, "2026-01-05T09:00:00Z");
+    let second = record(SessionOutcome::Cleared, "2026-01-05T10:00:00Z");
+    let missing = record(SessionOutcome::Skipped, "2026-01-05T11:00:00Z");
+    history
+        .append(&[first.clone(), second.clone(), first.clone()])
+        .unwrap();
+
+    history.remove(&[first.clone(), missing]).unwrap();
+
+    assert_eq!(history.load(), Ok(vec![first, second]));
+}
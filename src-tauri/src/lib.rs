@@ -1,61 +1,300 @@
+mod export;
+mod history;
+#[cfg(desktop)]
+mod menu;
+mod persistence;
+mod stats;
+#[cfg(test)]
+mod test_support;
+mod ticker;
 mod timer;
 
-use timer::{Phase, SharedTimerService, TimerState, create_timer_service};
+use export::ExportQuery;
+use history::HistoryStore;
+use persistence::StateFile;
+use stats::{Stats, StatsQuery};
+use tauri::{AppHandle, Manager};
+use timer::{
+    InterruptionKind, Phase, Program, SessionRecord, SharedTimerService, TaskTag, TimerConfig,
+    TimerError, TimerService, TimerState, create_timer_service,
+};
+
+const STATE_FILE_NAME: &str = "timer-state.json";
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+// Save the timer after a transition and append the sessions it ended to the
+// history, withdrawing any sessions an undo took back. A failed write is only
+// logged: the timer itself keeps working, it just won't survive a restart.
+fn persist(app: &AppHandle, service: &mut TimerService) {
+    if let Err(e) = app.state::<StateFile>().save(&service.snapshot()) {
+        log::warn!("Could not save timer state: {}", e);
+    }
+    let history = app.state::<HistoryStore>();
+    let undone = service.take_undone_sessions();
+    if let Err(e) = history.remove(&undone) {
+        log::warn!("Could not remove undone sessions from history: {}", e);
+    }
+    let finished = service.take_finished_sessions();
+    if let Err(e) = history.append(&finished) {
+        log::warn!("Could not save session history: {}", e);
+    }
+}
 
 #[tauri::command]
-fn get_state(timer: tauri::State<SharedTimerService>) -> Result<TimerState, String> {
-    let mut service = timer.lock().map_err(|e| e.to_string())?;
-    Ok(service.get_state())
+fn get_state(
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let before = (service.phase, service.status);
+    let state = service.get_state();
+    // The ticker normally completes sessions, but a read can get there first
+    if (state.phase, state.status) != before {
+        persist(&app, &mut service);
+    }
+    Ok(state)
 }
 
 #[tauri::command]
-fn start_timer(timer: tauri::State<SharedTimerService>) -> Result<TimerState, String> {
-    let mut service = timer.lock().map_err(|e| e.to_string())?;
-    service.start()
+fn start_timer(
+    task: Option<String>,
+    project: Option<String>,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let tag = TaskTag::new(task, project);
+    let state = if tag.is_empty() {
+        service.undoable(TimerService::start)?
+    } else {
+        service.undoable(|service| service.start_with_tag(Some(tag)))?
+    };
+    persist(&app, &mut service);
+    Ok(state)
 }
 
 #[tauri::command]
-fn pause_timer(timer: tauri::State<SharedTimerService>) -> Result<TimerState, String> {
-    let mut service = timer.lock().map_err(|e| e.to_string())?;
-    service.pause()
+fn pause_timer(
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(TimerService::pause)?;
+    persist(&app, &mut service);
+    Ok(state)
 }
 
 #[tauri::command]
-fn resume_timer(timer: tauri::State<SharedTimerService>) -> Result<TimerState, String> {
-    let mut service = timer.lock().map_err(|e| e.to_string())?;
-    service.resume()
+fn resume_timer(
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(TimerService::resume)?;
+    persist(&app, &mut service);
+    Ok(state)
 }
 
 #[tauri::command]
-fn clear_timer(timer: tauri::State<SharedTimerService>) -> Result<TimerState, String> {
-    let mut service = timer.lock().map_err(|e| e.to_string())?;
-    service.clear()
+fn adjust_remaining(
+    delta_secs: i32,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(|service| service.adjust_remaining(delta_secs))?;
+    persist(&app, &mut service);
+    Ok(state)
 }
 
 #[tauri::command]
-fn set_phase(phase: String, timer: tauri::State<SharedTimerService>) -> Result<TimerState, String> {
+fn stop_work(
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(TimerService::stop_work)?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+#[tauri::command]
+fn clear_timer(
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(TimerService::clear)?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+/// End the current session and move on to the next phase.
+#[tauri::command]
+fn skip(app: AppHandle, timer: tauri::State<SharedTimerService>) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(TimerService::skip)?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+/// Switch to a phase.
+#[tauri::command]
+fn set_phase(
+    phase: String,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
     let phase_enum = match phase.to_lowercase().as_str() {
         "work" => Phase::Work,
         "break" => Phase::Break,
-        _ => return Err("Invalid phase. Use 'work' or 'break'.".to_string()),
+        "longbreak" => Phase::LongBreak,
+        _ => return Err(TimerError::InvalidPhase { phase }),
     };
 
-    let mut service = timer.lock().map_err(|e| e.to_string())?;
-    service.set_phase(phase_enum);
+    let mut service = timer.lock()?;
+    // Switching to the current phase changes nothing, so leaves nothing to undo
+    if service.get_state().phase != phase_enum {
+        service.undoable(|service| {
+            service.set_phase(phase_enum);
+            Ok(())
+        })?;
+    }
+    persist(&app, &mut service);
     Ok(service.get_state())
 }
 
+/// Jump to a step of the current program.
+#[tauri::command]
+fn set_step(
+    step: usize,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undoable(|service| service.set_step(step))?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+/// Go back to the state before the last timer action.
+#[tauri::command]
+fn undo(app: AppHandle, timer: tauri::State<SharedTimerService>) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.undo()?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+#[tauri::command]
+fn record_interruption(
+    kind: InterruptionKind,
+    note: Option<String>,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.record_interruption(kind, note)?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+#[tauri::command]
+fn get_config(timer: tauri::State<SharedTimerService>) -> Result<TimerConfig, TimerError> {
+    let service = timer.lock()?;
+    Ok(service.config())
+}
+
+#[tauri::command]
+fn update_config(
+    config: TimerConfig,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.set_config(config)?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+#[tauri::command]
+fn get_program(timer: tauri::State<SharedTimerService>) -> Result<Option<Program>, TimerError> {
+    let service = timer.lock()?;
+    Ok(service.program().cloned())
+}
+
+#[tauri::command]
+fn set_program(
+    program: Option<Program>,
+    app: AppHandle,
+    timer: tauri::State<SharedTimerService>,
+) -> Result<TimerState, TimerError> {
+    let mut service = timer.lock()?;
+    let state = service.set_program(program)?;
+    persist(&app, &mut service);
+    Ok(state)
+}
+
+#[tauri::command]
+fn get_history(history: tauri::State<HistoryStore>) -> Result<Vec<SessionRecord>, TimerError> {
+    history
+        .load()
+        .map_err(|reason| TimerError::HistoryUnavailable { reason })
+}
+
+#[tauri::command]
+fn get_stats(query: StatsQuery, history: tauri::State<HistoryStore>) -> Result<Stats, TimerError> {
+    let records = history
+        .load()
+        .map_err(|reason| TimerError::HistoryUnavailable { reason })?;
+    stats::compute_stats(&records, &query, &chrono::Local)
+}
+
+/// Write the sessions in the query's range to the downloads directory and
+/// return the path of the new file.
+#[tauri::command]
+fn export_history(
+    query: ExportQuery,
+    app: AppHandle,
+    history: tauri::State<HistoryStore>,
+) -> Result<String, TimerError> {
+    let records = history
+        .load()
+        .map_err(|reason| TimerError::HistoryUnavailable { reason })?;
+    let contents = export::render(&records, &query, &chrono::Local)?;
+
+    let export_failed = |e: &dyn std::fmt::Display| TimerError::ExportFailed {
+        reason: e.to_string(),
+    };
+    let dir = app.path().download_dir().map_err(|e| export_failed(&e))?;
+    let path = dir.join(query.file_name());
+    std::fs::write(&path, contents).map_err(|e| export_failed(&e))?;
+    Ok(path.display().to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .manage(create_timer_service())
         .invoke_handler(tauri::generate_handler![
             get_state,
             start_timer,
             pause_timer,
             resume_timer,
+            adjust_remaining,
+            stop_work,
             clear_timer,
-            set_phase
+            skip,
+            set_phase,
+            set_step,
+            undo,
+            record_interruption,
+            get_config,
+            update_config,
+            get_program,
+            set_program,
+            get_history,
+            get_stats,
+            export_history
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -65,6 +304,20 @@ pub fn run() {
                         .build(),
                 )?;
             }
+
+            // Restore the previous session, counting the time the app was closed
+            let data_dir = app.path().app_data_dir()?;
+            let state_file = StateFile::new(data_dir.join(STATE_FILE_NAME));
+            let timer = create_timer_service(state_file.load());
+            app.manage(state_file);
+            app.manage(HistoryStore::new(data_dir.join(HISTORY_FILE_NAME)));
+            if let Ok(mut service) = timer.lock() {
+                persist(app.handle(), &mut service);
+            }
+            app.manage(timer);
+            ticker::spawn(app.handle().clone());
+            #[cfg(desktop)]
+            menu::install(app.handle())?;
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,35 @@
+use tauri::menu::{Menu, MenuEvent, MenuItem, Submenu};
+use tauri::{AppHandle, Emitter};
+
+const EXPORT_HISTORY_ID: &str = "export-history";
+
+/// Tauri event asking the frontend to show the export dialog
+pub const EXPORT_REQUESTED_EVENT: &str = "export-history-requested";
+
+/// Add a History menu next to the platform's default menus.
+pub fn install(app: &AppHandle) -> tauri::Result<()> {
+    let export = MenuItem::with_id(
+        app,
+        EXPORT_HISTORY_ID,
+        "Export History…",
+        true,
+        Some("CmdOrCtrl+E"),
+    )?;
+    let history = Submenu::with_items(app, "History", true, &[&export])?;
+
+    let menu = Menu::default(app)?;
+    menu.append(&history)?;
+    app.set_menu(menu)?;
+    app.on_menu_event(handle_event);
+    Ok(())
+}
+
+// The range and format are chosen in the frontend, which then calls
+// `export_history`
+fn handle_event(app: &AppHandle, event: MenuEvent) {
+    if event.id() == EXPORT_HISTORY_ID {
+        if let Err(e) = app.emit(EXPORT_REQUESTED_EVENT, ()) {
+            log::warn!("Could not emit {}: {}", EXPORT_REQUESTED_EVENT, e);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::timer::TimerSnapshot;
+
+/// JSON file in the app data directory holding the last timer snapshot.
+pub struct StateFile {
+    path: PathBuf,
+}
+
+impl StateFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Read the saved snapshot. A missing or unreadable file is not an error:
+    /// the app then simply starts from a fresh timer.
+    pub fn load(&self) -> Option<TimerSnapshot> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return None,
+            Err(e) => {
+                log::warn!("Could not read {}: {}", self.path.display(), e);
+                return None;
+            }
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                log::warn!(
+                    "Ignoring invalid timer state {}: {}",
+                    self.path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, snapshot: &TimerSnapshot) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(snapshot).map_err(|e| e.to_string())?;
+
+        // Write to a temporary file first so a crash mid-write never leaves a
+        // truncated state file behind
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+        fs::rename(&tmp_path, &self.path).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests;
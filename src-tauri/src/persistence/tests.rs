@@ -0,0 +1,53 @@
+//! Unit tests for the timer state file
+
+use super::*;
+use crate::test_support::TempDir;
+use crate::timer::TimerService;
+
+// Helper: state file in a fresh temporary directory
+fn temp_state_file(name: &str) -> (StateFile, TempDir) {
+    let dir = TempDir::new(name);
+    (StateFile::new(dir.join("timer-state.json")), dir)
+}
+
+#[test]
+fn test_save_then_load_round_trips_snapshot() {
+    let (file, _dir) = temp_state_file("round-trip");
+    let mut service = TimerService::new();
+    service.start().unwrap();
+    service.pause().unwrap();
+    let snapshot = service.snapshot();
+
+    file.save(&snapshot).unwrap();
+    assert_eq!(file.load(), Some(snapshot));
+}
+
+#[test]
+fn test_load_missing_file_returns_none() {
+    let (file, _dir) = temp_state_file("missing");
+
+    assert_eq!(file.load(), None);
+}
+
+#[test]
+fn test_load_invalid_file_returns_none() {
+    let (file, dir) = temp_state_file("invalid");
+    fs::create_dir_all(dir.path()).unwrap();
+    fs::write(dir.join("timer-state.json"), "{ not json").unwrap();
+
+    assert_eq!(file.load(), None);
+}
+
+#[test]
+fn test_save_overwrites_previous_snapshot() {
+    let (file, dir) = temp_state_file("overwrite");
+    let mut service = TimerService::new();
+    file.save(&service.snapshot()).unwrap();
+
+    service.set_phase(crate::timer::Phase::Break);
+    let snapshot = service.snapshot();
+    file.save(&snapshot).unwrap();
+
+    assert_eq!(file.load(), Some(snapshot));
+    assert!(!dir.join("timer-state.json.tmp").exists());
+}
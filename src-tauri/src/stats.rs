@@ -0,0 +1,192 @@
+use chrono::{Datelike, Days, Months, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::timer::{InterruptionKind, Phase, SessionOutcome, SessionRecord, TimerError};
+
+/// Size of the periods a statistics range is split into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Bucket {
+    Day,
+    /// ISO week, starting on Monday
+    Week,
+    Month,
+}
+
+/// Inclusive range of local dates to summarize.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub bucket: Bucket,
+    /// Completed work sessions a day needs to count towards a streak
+    pub daily_goal: u32,
+    /// Only count sessions tagged with this project
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+/// Totals for one bucket. Sessions are counted on the local day they started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketStats {
+    /// First day of the bucket, clipped to the queried range
+    pub start: NaiveDate,
+    /// Last day of the bucket (inclusive), clipped to the queried range
+    pub end: NaiveDate,
+    pub completed_work_sessions: u32,
+    /// Focused time of all work sessions, whatever their outcome
+    pub focused_minutes: u32,
+    /// Focused time of all breaks and long breaks
+    pub break_minutes: u32,
+    pub overtime_minutes: u32,
+    /// Average number of pauses per work session
+    pub average_pause_count: f64,
+    /// Interruptions of all work sessions
+    pub internal_interruptions: u32,
+    pub external_interruptions: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub buckets: Vec<BucketStats>,
+    pub total: BucketStats,
+    /// Longest run of consecutive days in the range meeting the daily goal
+    pub longest_streak_days: u32,
+}
+
+/// Summarize `records` over the query's range, splitting days in `tz`.
+pub fn compute_stats<Tz: TimeZone>(
+    records: &[SessionRecord],
+    query: &StatsQuery,
+    tz: &Tz,
+) -> Result<Stats, TimerError> {
+    if query.from > query.to {
+        return Err(TimerError::InvalidDateRange {
+            from: query.from,
+            to: query.to,
+        });
+    }
+
+    let dated: Vec<(NaiveDate, &SessionRecord)> = records
+        .iter()
+        .map(|record| (record.started_at.with_timezone(tz).date_naive(), record))
+        .filter(|(date, _)| (query.from..=query.to).contains(date))
+        .filter(|(_, record)| query.project.is_none() || record.tag.project == query.project)
+        .collect();
+
+    let mut buckets = Vec::new();
+    let mut start = bucket_start(query.from, query.bucket);
+    while start <= query.to {
+        let next = next_bucket_start(start, query.bucket);
+        let last = next
+            .and_then(|next| next.pred_opt())
+            .map_or(query.to, |last| last.min(query.to));
+        buckets.push(summarize(start.max(query.from), last, &dated));
+        match next {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+
+    Ok(Stats {
+        buckets,
+        total: summarize(query.from, query.to, &dated),
+        longest_streak_days: longest_streak(query, &dated),
+    })
+}
+
+fn summarize(
+    start: NaiveDate,
+    end: NaiveDate,
+    dated: &[(NaiveDate, &SessionRecord)],
+) -> BucketStats {
+    let mut completed_work_sessions = 0;
+    let mut work_sessions = 0;
+    let mut pauses = 0;
+    let mut focused_secs = 0u64;
+    let mut break_secs = 0u64;
+    let mut overtime_secs = 0u64;
+    let mut internal_interruptions = 0;
+    let mut external_interruptions = 0;
+
+    for (_, record) in dated
+        .iter()
+        .filter(|(date, _)| (start..=end).contains(date))
+    {
+        overtime_secs += record.overtime_secs as u64;
+        match record.phase {
+            Phase::Work => {
+                work_sessions += 1;
+                pauses += record.pause_count;
+                focused_secs += record.focused_secs as u64;
+                internal_interruptions += record.interruption_count(InterruptionKind::Internal);
+                external_interruptions += record.interruption_count(InterruptionKind::External);
+                if record.outcome == SessionOutcome::Completed {
+                    completed_work_sessions += 1;
+                }
+            }
+            Phase::Break | Phase::LongBreak => break_secs += record.focused_secs as u64,
+        }
+    }
+
+    BucketStats {
+        start,
+        end,
+        completed_work_sessions,
+        focused_minutes: (focused_secs / 60) as u32,
+        break_minutes: (break_secs / 60) as u32,
+        overtime_minutes: (overtime_secs / 60) as u32,
+        average_pause_count: if work_sessions == 0 {
+            0.0
+        } else {
+            pauses as f64 / work_sessions as f64
+        },
+        internal_interruptions,
+        external_interruptions,
+    }
+}
+
+fn longest_streak(query: &StatsQuery, dated: &[(NaiveDate, &SessionRecord)]) -> u32 {
+    let mut completed_per_day: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+    for (date, record) in dated {
+        if record.phase == Phase::Work && record.outcome == SessionOutcome::Completed {
+            *completed_per_day.entry(*date).or_default() += 1;
+        }
+    }
+
+    let mut longest = 0;
+    let mut current = 0;
+    for day in query.from.iter_days().take_while(|day| *day <= query.to) {
+        if completed_per_day.get(&day).copied().unwrap_or(0) >= query.daily_goal {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+fn bucket_start(date: NaiveDate, bucket: Bucket) -> NaiveDate {
+    match bucket {
+        Bucket::Day => date,
+        Bucket::Week => date - Days::new(date.weekday().num_days_from_monday() as u64),
+        Bucket::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+// None past the end of chrono's calendar
+fn next_bucket_start(start: NaiveDate, bucket: Bucket) -> Option<NaiveDate> {
+    match bucket {
+        Bucket::Day => start.checked_add_days(Days::new(1)),
+        Bucket::Week => start.checked_add_days(Days::new(7)),
+        Bucket::Month => start.checked_add_months(Months::new(1)),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,242 @@
+//! Unit tests for history statistics
+
+use super::*;
+use crate::timer::{Interruption, TaskTag};
+use chrono::{FixedOffset, Utc};
+
+fn date(text: &str) -> NaiveDate {
+    text.parse().unwrap()
+}
+
+// Helper: work session of `minutes` focused minutes starting at `started_at`
+fn work(started_at: &str, minutes: u32, outcome: SessionOutcome) -> SessionRecord {
+    SessionRecord {
+        planned_secs: 1500,
+        ..SessionRecord::sample(
+            Phase::Work,
+            outcome,
+            started_at.parse().unwrap(),
+            minutes * 60,
+        )
+    }
+}
+
+fn completed(started_at: &str) -> SessionRecord {
+    work(started_at, 25, SessionOutcome::Completed)
+}
+
+fn query(from: &str, to: &str, bucket: Bucket) -> StatsQuery {
+    StatsQuery {
+        from: date(from),
+        to: date(to),
+        bucket,
+        daily_goal: 1,
+        project: None,
+    }
+}
+
+#[test]
+fn test_daily_totals_split_by_phase_and_outcome() {
+    let records = vec![
+        SessionRecord {
+            pause_count: 2,
+            overtime_secs: 120,
+            interruptions: vec![Interruption {
+                kind: InterruptionKind::External,
+                at: "2026-01-05T09:10:00Z".parse().unwrap(),
+                note: None,
+            }],
+            ..completed("2026-01-05T09:00:00Z")
+        },
+        work("2026-01-05T10:00:00Z", 10, SessionOutcome::Cleared),
+        SessionRecord {
+            phase: Phase::Break,
+            focused_secs: 300,
+            ..completed("2026-01-05T09:25:00Z")
+        },
+    ];
+
+    let stats = compute_stats(
+        &records,
+        &query("2026-01-05", "2026-01-05", Bucket::Day),
+        &Utc,
+    )
+    .unwrap();
+
+    assert_eq!(
+        stats.total,
+        BucketStats {
+            start: date("2026-01-05"),
+            end: date("2026-01-05"),
+            completed_work_sessions: 1,
+            focused_minutes: 35,
+            break_minutes: 5,
+            overtime_minutes: 2,
+            average_pause_count: 1.0,
+            internal_interruptions: 0,
+            external_interruptions: 1,
+        }
+    );
+    assert_eq!(stats.buckets, vec![stats.total.clone()]);
+}
+
+#[test]
+fn test_empty_days_still_get_buckets() {
+    let records = vec![completed("2026-01-06T09:00:00Z")];
+
+    let stats = compute_stats(
+        &records,
+        &query("2026-01-05", "2026-01-07", Bucket::Day),
+        &Utc,
+    )
+    .unwrap();
+
+    let counts: Vec<u32> = stats
+        .buckets
+        .iter()
+        .map(|bucket| bucket.completed_work_sessions)
+        .collect();
+    assert_eq!(counts, vec![0, 1, 0]);
+    assert_eq!(stats.buckets[0].average_pause_count, 0.0);
+}
+
+#[test]
+fn test_week_buckets_start_on_monday_and_are_clipped() {
+    // 2026-01-07 is a Wednesday
+    let stats = compute_stats(&[], &query("2026-01-07", "2026-01-20", Bucket::Week), &Utc).unwrap();
+
+    let ranges: Vec<(NaiveDate, NaiveDate)> = stats
+        .buckets
+        .iter()
+        .map(|bucket| (bucket.start, bucket.end))
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            (date("2026-01-07"), date("2026-01-11")),
+            (date("2026-01-12"), date("2026-01-18")),
+            (date("2026-01-19"), date("2026-01-20")),
+        ]
+    );
+}
+
+#[test]
+fn test_month_buckets_follow_calendar_months() {
+    let records = vec![
+        completed("2026-01-31T09:00:00Z"),
+        completed("2026-02-01T09:00:00Z"),
+        completed("2026-02-28T09:00:00Z"),
+    ];
+
+    let stats = compute_stats(
+        &records,
+        &query("2026-01-15", "2026-03-10", Bucket::Month),
+        &Utc,
+    )
+    .unwrap();
+
+    assert_eq!(stats.buckets.len(), 3);
+    assert_eq!(stats.buckets[1].start, date("2026-02-01"));
+    assert_eq!(stats.buckets[1].end, date("2026-02-28"));
+    assert_eq!(stats.buckets[1].completed_work_sessions, 2);
+    assert_eq!(stats.total.completed_work_sessions, 3);
+}
+
+#[test]
+fn test_sessions_counted_on_local_start_day() {
+    // 23:30 UTC is already the next day nine hours east
+    let records = vec![completed("2026-01-05T23:30:00Z")];
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+
+    let stats = compute_stats(
+        &records,
+        &query("2026-01-05", "2026-01-06", Bucket::Day),
+        &tokyo,
+    )
+    .unwrap();
+
+    assert_eq!(stats.buckets[0].completed_work_sessions, 0);
+    assert_eq!(stats.buckets[1].completed_work_sessions, 1);
+}
+
+#[test]
+fn test_longest_streak_counts_days_meeting_goal() {
+    let records = vec![
+        completed("2026-01-05T09:00:00Z"),
+        completed("2026-01-05T10:00:00Z"),
+        completed("2026-01-06T09:00:00Z"),
+        completed("2026-01-06T10:00:00Z"),
+        // Only one session: breaks a goal of two
+        completed("2026-01-07T09:00:00Z"),
+        completed("2026-01-08T09:00:00Z"),
+        completed("2026-01-08T10:00:00Z"),
+        work("2026-01-08T11:00:00Z", 5, SessionOutcome::Cleared),
+    ];
+    let mut query = query("2026-01-05", "2026-01-08", Bucket::Week);
+    query.daily_goal = 2;
+
+    let stats = compute_stats(&records, &query, &Utc).unwrap();
+    assert_eq!(stats.longest_streak_days, 2);
+
+    query.daily_goal = 1;
+    let stats = compute_stats(&records, &query, &Utc).unwrap();
+    assert_eq!(stats.longest_streak_days, 4);
+}
+
+#[test]
+fn test_records_outside_range_are_ignored() {
+    let records = vec![
+        completed("2026-01-04T09:00:00Z"),
+        completed("2026-01-05T09:00:00Z"),
+        completed("2026-01-06T09:00:00Z"),
+    ];
+
+    let stats = compute_stats(
+        &records,
+        &query("2026-01-05", "2026-01-05", Bucket::Month),
+        &Utc,
+    )
+    .unwrap();
+
+    assert_eq!(stats.total.completed_work_sessions, 1);
+    assert_eq!(stats.buckets.len(), 1);
+}
+
+#[test]
+fn test_reversed_range_is_rejected() {
+    let result = compute_stats(&[], &query("2026-01-06", "2026-01-05", Bucket::Day), &Utc);
+
+    assert_eq!(
+        result.unwrap_err(),
+        TimerError::InvalidDateRange {
+            from: date("2026-01-06"),
+            to: date("2026-01-05"),
+        }
+    );
+}
+
+#[test]
+fn test_project_filter_only_counts_tagged_sessions() {
+    let tagged = |project: &str| SessionRecord {
+        tag: TaskTag {
+            task: None,
+            project: Some(project.to_string()),
+        },
+        ..completed("2026-01-05T09:00:00Z")
+    };
+    let records = vec![
+        tagged("Acme"),
+        tagged("Acme"),
+        tagged("Other"),
+        completed("2026-01-05T12:00:00Z"),
+    ];
+    let mut query = query("2026-01-05", "2026-01-05", Bucket::Day);
+
+    query.project = Some("Acme".to_string());
+    let stats = compute_stats(&records, &query, &Utc).unwrap();
+    assert_eq!(stats.total.completed_work_sessions, 2);
+
+    query.project = None;
+    let stats = compute_stats(&records, &query, &Utc).unwrap();
+    assert_eq!(stats.total.completed_work_sessions, 4);
+}
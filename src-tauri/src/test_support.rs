@@ -0,0 +1,37 @@
+//! Helpers shared by the unit tests of several modules
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fresh directory under the system temp directory, removed again when
+/// dropped. `name` keeps tests that run in parallel apart.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "pomodoro-timer-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&path);
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of a file in the directory, which is not created until written
+    pub fn join(&self, file: &str) -> PathBuf {
+        self.path.join(file)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,56 @@
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::timer::{SharedTimerService, TimerState};
+
+/// Tauri event carrying the full `TimerState` whenever it changes
+pub const STATE_EVENT: &str = "timer-state";
+
+// Several ticks per second so a completion is noticed well within a second
+// and the countdown never visibly skips a number
+const TICK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Drive the timer from the backend: advance it on a fixed interval, emit
+/// `STATE_EVENT` when the state changes and one event per `TimerEvent`.
+///
+/// The webview may be throttled or hidden, so completion must not depend on
+/// the frontend asking for the state.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || {
+        let mut last_state: Option<TimerState> = None;
+        loop {
+            thread::sleep(TICK_INTERVAL);
+            tick(&app, &mut last_state);
+        }
+    });
+}
+
+fn tick(app: &AppHandle, last_state: &mut Option<TimerState>) {
+    let timer = app.state::<SharedTimerService>();
+    let (state, events) = {
+        let Ok(mut service) = timer.lock() else {
+            return;
+        };
+        let before = (service.phase, service.status);
+        let state = service.get_state();
+        if (state.phase, state.status) != before {
+            crate::persist(app, &mut service);
+        }
+        (state, service.take_events())
+    };
+
+    for event in events {
+        if let Err(e) = app.emit(event.name(), &event) {
+            log::warn!("Could not emit {}: {}", event.name(), e);
+        }
+    }
+
+    if last_state.as_ref() != Some(&state) {
+        if let Err(e) = app.emit(STATE_EVENT, &state) {
+            log::warn!("Could not emit {}: {}", STATE_EVENT, e);
+        }
+        *last_state = Some(state);
+    }
+}
@@ -1,15 +1,94 @@
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use std::time::Instant;
-
+use std::time::{Duration, Instant};
+
+mod action;
+mod adjust;
+mod clock;
+mod config;
+mod error;
+mod event;
+mod flowtime;
+mod goal;
+mod overtime;
+mod program;
+mod session;
+mod snapshot;
+mod suspend;
+mod undo;
+
+pub use action::TimerAction;
+pub use clock::{Clock, SystemClock};
+pub use config::{CompletionAction, OvertimePolicy, SuspendPolicy, TimerConfig, TimerMode};
+pub use error::TimerError;
+pub use event::TimerEvent;
+pub use program::{Program, ProgramStep};
+pub use session::{
+    ActiveSession, Adjustment, Interruption, InterruptionKind, SessionOutcome, SessionRecord,
+    Suspension, TaskTag,
+};
+pub use snapshot::TimerSnapshot;
+use undo::UndoStack;
+
+// Default phase durations, overridable through TimerConfig
 const WORK_DURATION_SECS: u32 = 1500; // 25 minutes
 const BREAK_DURATION_SECS: u32 = 300; // 5 minutes
+const LONG_BREAK_DURATION_SECS: u32 = 900; // 15 minutes
+const LONG_BREAK_INTERVAL: u32 = 4; // Long break after every 4th work session
+const DAILY_GOAL: u32 = 8; // Completed work sessions per day
+const ROLLOVER_HOUR: u32 = 4; // A new day starts at 4 AM local time
+const OVERTIME_CAP_SECS: u32 = 3599; // Overtime is shown up to 59:59 by default
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum Phase {
     Work,
     Break,
+    LongBreak,
+}
+
+impl Phase {
+    fn ready_status(self) -> Status {
+        match self {
+            Phase::Work => Status::WorkReady,
+            Phase::Break => Status::BreakReady,
+            Phase::LongBreak => Status::LongBreakReady,
+        }
+    }
+
+    fn ready_label(self) -> &'static str {
+        match self {
+            Phase::Work => "Ready to work",
+            Phase::Break => "Ready to break",
+            Phase::LongBreak => "Ready for long break",
+        }
+    }
+
+    fn running_label(self) -> &'static str {
+        match self {
+            Phase::Work => "Working",
+            Phase::Break => "Break time",
+            Phase::LongBreak => "Long break time",
+        }
+    }
+
+    fn completed_label(self) -> &'static str {
+        match self {
+            Phase::Work => "Work completed",
+            Phase::Break => "Break completed",
+            Phase::LongBreak => "Long break completed",
+        }
+    }
+
+    // Lowercase name used inside labels such as "Paused (work)"
+    fn label_name(self) -> &'static str {
+        match self {
+            Phase::Work => "work",
+            Phase::Break => "break",
+            Phase::LongBreak => "long break",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,77 +96,362 @@ pub enum Phase {
 pub enum Status {
     WorkReady,
     BreakReady,
+    LongBreakReady,
     Running,
     Paused,
     Complete,
     OvertimePaused,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TimerState {
     pub phase: Phase,
     pub status: Status,
+    /// Zero while counting up
     pub remaining_secs: u32,
+    /// Remaining time to the millisecond, for smooth displays; `remaining_secs`
+    /// is this rounded up
+    pub remaining_millis: u64,
+    /// Zero while counting up, as the session has no fixed end
     pub duration_secs: u32,
+    pub counting_up: bool,
+    /// Time worked so far, only while counting up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_secs: Option<u32>,
     pub completion_flag: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub started_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub paused_at: Option<String>,
+    /// Projected finish time, only while running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<String>,
     pub state_label: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub overtime_secs: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub overtime_paused_secs: Option<u32>,
+    pub completed_work_sessions: u32,
+    /// Work sessions completed since the last daily rollover
+    pub completed_today: u32,
+    pub daily_goal: u32,
+    pub next_phase: Phase,
+    /// Position in the program, if one is followed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_step: Option<ProgramStep>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Interruptions of the current session
+    pub internal_interruptions: u32,
+    pub external_interruptions: u32,
+    pub can_undo: bool,
+    /// Actions that succeed in this state, and those among them that discard
+    /// unfinished time and should be confirmed
+    pub available_actions: Vec<TimerAction>,
+    pub destructive_actions: Vec<TimerAction>,
 }
 
 pub struct TimerService {
     pub(crate) phase: Phase,
     pub(crate) status: Status,
-    pub(crate) remaining_secs: u32,
+    // Time left, or the time counted so far while counting up
+    pub(crate) remaining: Duration,
     duration_secs: u32,
+    // Whether the current or parked work session counts up; decided when it
+    // starts so a config change does not flip a session in progress
+    pub(crate) counting_up: bool,
+    // Break earned by the last count-up work session, until a break or more
+    // work starts
+    pub(crate) flow_break_secs: Option<u32>,
     completion_flag: bool,
     pub(crate) started_instant: Option<Instant>,
     pub(crate) completed_at: Option<Instant>,
-    pub(crate) overtime_paused_secs: Option<u32>,
-    pub(crate) paused_work_secs: Option<u32>,
-    pub(crate) paused_break_secs: Option<u32>,
+    // Wall-clock start of the current session, kept across pause and resume
+    pub(crate) session_started_at: Option<DateTime<Utc>>,
+    pub(crate) paused_at: Option<DateTime<Utc>>,
+    pub(crate) overtime_paused: Option<Duration>,
+    pub(crate) paused_work: Option<Duration>,
+    pub(crate) paused_break: Option<Duration>,
+    pub(crate) paused_long_break: Option<Duration>,
+    // Program step each phase's paused time was parked from
+    pub(crate) paused_work_step: Option<usize>,
+    pub(crate) paused_break_step: Option<usize>,
+    pub(crate) paused_long_break_step: Option<usize>,
+    // Task of each phase, kept like the paused time so it survives switching
+    pub(crate) work_tag: TaskTag,
+    pub(crate) break_tag: TaskTag,
+    pub(crate) long_break_tag: TaskTag,
+    pub(crate) completed_work_sessions: u32,
+    // Day the daily goal count belongs to, and the count itself
+    pub(crate) goal_day: Option<NaiveDate>,
+    pub(crate) completed_today: u32,
+    // Whether the goal of `goal_day` was already announced
+    pub(crate) goal_announced: bool,
     state_label: String,
+    config: TimerConfig,
+    // Program followed instead of the default alternation, and the index of
+    // its current step
+    pub(crate) program: Option<Program>,
+    pub(crate) step: usize,
+    // Session in progress, and ended sessions not yet picked up by
+    // `take_finished_sessions`
+    pub(crate) session: Option<ActiveSession>,
+    finished_sessions: Vec<SessionRecord>,
+    // States to go back to, newest last, and history records withdrawn by
+    // an undo but not yet picked up by `take_undone_sessions`
+    undo_stack: UndoStack,
+    undone_sessions: Vec<SessionRecord>,
+    // Overtime reminders sent since the session completed
+    pub(crate) overtime_reminders: u32,
+    // Events not yet picked up by `take_events`
+    events: Vec<TimerEvent>,
+    // Monotonic, boot and wall clocks as of the last update, to notice time
+    // spent asleep
+    last_check: Option<(Instant, Duration, DateTime<Utc>)>,
+    clock: Box<dyn Clock>,
 }
 
 impl TimerService {
     pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
         Self {
             phase: Phase::Work,
             status: Status::WorkReady,
-            remaining_secs: WORK_DURATION_SECS,
+            remaining: Duration::from_secs(WORK_DURATION_SECS.into()),
             duration_secs: WORK_DURATION_SECS,
+            counting_up: false,
+            flow_break_secs: None,
             completion_flag: false,
             started_instant: None,
             completed_at: None,
-            overtime_paused_secs: None,
-            paused_work_secs: None,
-            paused_break_secs: None,
+            session_started_at: None,
+            paused_at: None,
+            overtime_paused: None,
+            paused_work: None,
+            paused_break: None,
+            paused_long_break: None,
+            paused_work_step: None,
+            paused_break_step: None,
+            paused_long_break_step: None,
+            work_tag: TaskTag::default(),
+            break_tag: TaskTag::default(),
+            long_break_tag: TaskTag::default(),
+            completed_work_sessions: 0,
+            goal_day: None,
+            completed_today: 0,
+            goal_announced: false,
             state_label: "Ready to work".to_string(),
+            config: TimerConfig::default(),
+            program: None,
+            step: 0,
+            session: None,
+            finished_sessions: Vec::new(),
+            undo_stack: UndoStack::new(),
+            undone_sessions: Vec::new(),
+            overtime_reminders: 0,
+            events: Vec::new(),
+            last_check: None,
+            clock: Box::new(clock),
+        }
+    }
+
+    pub fn config(&self) -> TimerConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: TimerConfig) -> Result<TimerState, TimerError> {
+        config.validate()?;
+        self.config = config;
+
+        // Only a fresh Ready state picks up the new duration right away;
+        // sessions in progress finish with the duration they started with
+        if self.status == self.phase.ready_status() {
+            self.load_duration();
+        }
+
+        Ok(self.get_state())
+    }
+
+    fn phase_duration(&self, phase: Phase) -> u32 {
+        if let Some(step) = self.current_step().filter(|step| step.phase == phase) {
+            return step.duration_secs;
+        }
+        match phase {
+            // Counting up starts from zero
+            Phase::Work if self.flowtime_work() => 0,
+            Phase::Work => self.config.work_duration_secs,
+            Phase::Break => self
+                .flow_break_secs
+                .unwrap_or(self.config.break_duration_secs),
+            Phase::LongBreak => self
+                .flow_break_secs
+                .unwrap_or(self.config.long_break_duration_secs),
+        }
+    }
+
+    // Load the full duration of the current phase for a fresh session
+    fn load_duration(&mut self) {
+        if self.phase == Phase::Work {
+            self.counting_up = self.flowtime_work();
+        }
+        self.duration_secs = self.phase_duration(self.phase);
+        self.remaining = Duration::from_secs(self.duration_secs.into());
+    }
+
+    // Remaining time in whole seconds. A countdown rounds up, so it shows its
+    // full duration when started and zero only once done; time counted up
+    // rounds down.
+    pub(crate) fn remaining_secs(&self) -> u32 {
+        if self.counts_up() {
+            self.remaining.as_secs() as u32
+        } else {
+            secs_rounded_up(self.remaining)
         }
     }
 
+    fn paused(&self, phase: Phase) -> Option<Duration> {
+        match phase {
+            Phase::Work => self.paused_work,
+            Phase::Break => self.paused_break,
+            Phase::LongBreak => self.paused_long_break,
+        }
+    }
+
+    fn paused_mut(&mut self, phase: Phase) -> &mut Option<Duration> {
+        match phase {
+            Phase::Work => &mut self.paused_work,
+            Phase::Break => &mut self.paused_break,
+            Phase::LongBreak => &mut self.paused_long_break,
+        }
+    }
+
+    // Step the paused time of `phase` was parked from, if it has any
+    fn paused_step(&self, phase: Phase) -> Option<usize> {
+        let step = match phase {
+            Phase::Work => self.paused_work_step,
+            Phase::Break => self.paused_break_step,
+            Phase::LongBreak => self.paused_long_break_step,
+        };
+        self.paused(phase).and(step)
+    }
+
+    fn paused_step_mut(&mut self, phase: Phase) -> &mut Option<usize> {
+        match phase {
+            Phase::Work => &mut self.paused_work_step,
+            Phase::Break => &mut self.paused_break_step,
+            Phase::LongBreak => &mut self.paused_long_break_step,
+        }
+    }
+
+    // Remaining time when the current running stretch began: the time kept
+    // by the last pause, or else the full duration
+    fn initial_remaining(&self) -> Duration {
+        self.paused(self.phase)
+            .unwrap_or(Duration::from_secs(self.duration_secs.into()))
+    }
+
+    fn tag(&self, phase: Phase) -> &TaskTag {
+        match phase {
+            Phase::Work => &self.work_tag,
+            Phase::Break => &self.break_tag,
+            Phase::LongBreak => &self.long_break_tag,
+        }
+    }
+
+    fn tag_mut(&mut self, phase: Phase) -> &mut TaskTag {
+        match phase {
+            Phase::Work => &mut self.work_tag,
+            Phase::Break => &mut self.break_tag,
+            Phase::LongBreak => &mut self.long_break_tag,
+        }
+    }
+
+    /// Phase that follows the current one: the next step of the program, if
+    /// any; otherwise work alternates with breaks, and every
+    /// `long_break_interval`-th work session is followed by a long break.
+    pub fn next_phase(&self) -> Phase {
+        if let Some(step) = self.next_step() {
+            return step.phase;
+        }
+        match self.phase {
+            Phase::Work => {
+                // Count the current work session unless it is already counted
+                let work_done = if matches!(self.status, Status::Complete | Status::OvertimePaused)
+                {
+                    self.completed_work_sessions
+                } else {
+                    self.completed_work_sessions + 1
+                };
+                if work_done % self.config.long_break_interval == 0 {
+                    Phase::LongBreak
+                } else {
+                    Phase::Break
+                }
+            }
+            Phase::Break | Phase::LongBreak => Phase::Work,
+        }
+    }
+
+    // Phase `skip` moves on to. Unlike in `next_phase`, work skipped before
+    // it completed is not counted, so it never earns the long break.
+    pub(crate) fn phase_after_skip(&self) -> Phase {
+        let unfinished_work = self.phase == Phase::Work
+            && !matches!(self.status, Status::Complete | Status::OvertimePaused);
+        if unfinished_work && self.next_step().is_none() {
+            Phase::Break
+        } else {
+            self.next_phase()
+        }
+    }
+
+    // Monotonic time elapsed since `instant`
+    fn elapsed_since(&self, instant: Instant) -> Duration {
+        self.clock.now().saturating_duration_since(instant)
+    }
+
+    // Wall-clock time at which a monotonic instant happened
+    fn wall_of(&self, instant: Instant) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.elapsed_since(instant)).unwrap_or_default();
+        self.clock.wall_now() - elapsed
+    }
+
+    // Monotonic instant `duration` ago, or now if that is before the clock's origin
+    fn instant_ago(&self, duration: Duration) -> Instant {
+        let now = self.clock.now();
+        now.checked_sub(duration).unwrap_or(now)
+    }
+
+    /// Drain the events queued since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<TimerEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     pub fn get_state(&mut self) -> TimerState {
         self.update_remaining();
 
-        // Calculate overtime if applicable
-        let overtime_secs = match self.status {
-            Status::Complete => self.completed_at.map(|completed| {
-                let elapsed = completed.elapsed().as_secs() as u32;
-                std::cmp::min(elapsed, 3599) // Cap at 59:59
-            }),
-            Status::OvertimePaused => self.overtime_paused_secs,
-            _ => None,
-        };
+        let overtime_secs = self.overtime_secs();
 
         let overtime_paused_secs = if self.status == Status::OvertimePaused {
-            self.overtime_paused_secs
+            self.overtime_paused
+                .map(|overtime| overtime.as_secs() as u32)
+        } else {
+            None
+        };
+
+        let counting_up = self.counts_up();
+        let ends_at = if self.status == Status::Running && !counting_up {
+            let remaining = chrono::Duration::from_std(self.remaining).unwrap_or_default();
+            Some(self.clock.wall_now() + remaining)
         } else {
             None
         };
@@ -95,299 +459,365 @@ impl TimerService {
         TimerState {
             phase: self.phase,
             status: self.status,
-            remaining_secs: self.remaining_secs,
+            remaining_secs: if counting_up {
+                0
+            } else {
+                self.remaining_secs()
+            },
+            remaining_millis: if counting_up {
+                0
+            } else {
+                self.remaining.as_millis() as u64
+            },
             duration_secs: self.duration_secs,
+            counting_up,
+            elapsed_secs: counting_up.then(|| self.remaining_secs()),
             completion_flag: self.completion_flag,
-            started_at: None,
-            paused_at: None,
+            started_at: self.session_started_at.map(to_rfc3339),
+            paused_at: self.paused_at.map(to_rfc3339),
+            ends_at: ends_at.map(to_rfc3339),
             state_label: self.state_label.clone(),
             overtime_secs,
             overtime_paused_secs,
+            completed_work_sessions: self.completed_work_sessions,
+            completed_today: self.completed_today(),
+            daily_goal: self.config.daily_goal,
+            next_phase: self.next_phase(),
+            step_index: self.program.as_ref().map(|_| self.step),
+            step_name: self.current_step().and_then(|step| step.name.clone()),
+            next_step: self.next_step().cloned(),
+            task: self.tag(self.phase).task.clone(),
+            project: self.tag(self.phase).project.clone(),
+            internal_interruptions: self.interruption_count(InterruptionKind::Internal),
+            external_interruptions: self.interruption_count(InterruptionKind::External),
+            can_undo: self.can_undo(),
+            available_actions: self.available_actions(),
+            destructive_actions: self.destructive_actions(),
         }
     }
 
     pub(crate) fn update_remaining(&mut self) {
-        if self.status == Status::Running {
-            if let Some(start) = self.started_instant {
-                let elapsed = start.elapsed().as_secs() as u32;
-                let initial = match self.phase {
-                    Phase::Work => self.paused_work_secs.unwrap_or(self.duration_secs),
-                    Phase::Break => self.paused_break_secs.unwrap_or(self.duration_secs),
-                };
-
-                if elapsed >= initial {
-                    self.remaining_secs = 0;
-                    // Pass the exact completion time (when timer reached zero)
-                    let completion_time = start + std::time::Duration::from_secs(initial as u64);
-                    self.handle_completion(completion_time);
-                } else {
-                    self.remaining_secs = initial - elapsed;
-                }
+        self.detect_suspend();
+        // Loop because an auto-started session may itself have completed
+        // already if the timer was not observed for a long time
+        while self.status == Status::Running {
+            let Some(start) = self.started_instant else {
+                break;
+            };
+            let elapsed = self.elapsed_since(start);
+            let initial = self.initial_remaining();
+
+            if self.counts_up() {
+                // No end to reach: count on from the time worked before the
+                // last resume
+                self.remaining = initial.saturating_add(elapsed);
+                break;
+            } else if elapsed >= initial {
+                self.remaining = Duration::ZERO;
+                // Pass the exact completion time (when timer reached zero)
+                let completion_time = start + initial;
+                self.handle_completion(completion_time);
+            } else {
+                self.remaining = initial - elapsed;
+                break;
             }
         }
+        self.apply_overtime_policy();
+        self.remind_of_overtime();
     }
 
     pub(crate) fn handle_completion(&mut self, completion_time: Instant) {
+        let action = match self.phase {
+            Phase::Work => self.config.on_work_complete,
+            Phase::Break | Phase::LongBreak => self.config.on_break_complete,
+        };
+
+        let completed_wall = self.wall_of(completion_time);
+        self.record_completion(completed_wall);
+        if action != CompletionAction::Stay {
+            // Moving on right away leaves no overtime to record
+            self.finish_session(SessionOutcome::Completed);
+        }
+
         self.completion_flag = true;
-        self.remaining_secs = 0;
+        self.remaining = Duration::ZERO;
+        // Time kept by an earlier pause is used up, so switching back to this
+        // phase later must not bring the session back as paused
+        *self.paused_mut(self.phase) = None;
         self.status = Status::Complete;
         self.started_instant = None;
         self.completed_at = Some(completion_time);
-        self.overtime_paused_secs = None;
+        self.paused_at = None;
+        self.overtime_paused = None;
+        self.overtime_reminders = 0;
 
-        // Stay in current phase, update label
-        self.state_label = match self.phase {
-            Phase::Work => "Work completed".to_string(),
-            Phase::Break => "Break completed".to_string(),
-        };
+        if self.phase == Phase::Work {
+            self.completed_work_sessions += 1;
+        }
+
+        self.events.push(TimerEvent::Completed {
+            phase: self.phase,
+            completed_at: to_rfc3339(completed_wall),
+        });
+        if self.phase == Phase::Work {
+            self.record_goal_progress(completed_wall);
+        }
+
+        if action == CompletionAction::Stay {
+            // Stay in current phase, update label
+            self.state_label = self.phase.completed_label().to_string();
+
+            // Note: Do NOT clear the paused time of other phases
+            // Note: Do NOT change self.phase or self.duration_secs
+            return;
+        }
+
+        // Advance to the next phase, keeping the flag set so the completion
+        // chime still plays
+        let next = self.next_phase();
+        self.advance_step();
+        self.enter_phase(next);
+        self.completion_flag = true;
 
-        // Note: Do NOT clear paused_work_secs or paused_break_secs
-        // Note: Do NOT change self.phase or self.duration_secs
+        if action == CompletionAction::AutoStart {
+            // Count from the moment the previous session reached zero, so no
+            // time is lost between polls
+            self.status = Status::Running;
+            self.started_instant = Some(completion_time);
+            self.session_started_at = Some(completed_wall);
+            self.begin_session(completed_wall);
+            self.state_label = next.running_label().to_string();
+        }
     }
 
-    pub fn start(&mut self) -> Result<TimerState, String> {
-        if self.status == Status::Running {
-            return Err("Timer already running".to_string());
-        }
-
-        // Phase-aware start: start work or break based on current status
-        match self.status {
-            Status::WorkReady => {
-                // Start work session
-                self.phase = Phase::Work;
-                self.status = Status::Running;
-                self.duration_secs = WORK_DURATION_SECS;
-                self.remaining_secs = WORK_DURATION_SECS;
-                self.completion_flag = false;
-                self.state_label = "Working".to_string();
-                self.started_instant = Some(Instant::now());
-                self.paused_work_secs = None;
-                // Preserve paused_break_secs for switching back to break later
-            }
-            Status::Complete | Status::OvertimePaused => {
-                // Restart the current phase (stay in work or break)
-                match self.phase {
-                    Phase::Work => {
-                        self.status = Status::Running;
-                        self.duration_secs = WORK_DURATION_SECS;
-                        self.remaining_secs = WORK_DURATION_SECS;
-                        self.completion_flag = false;
-                        self.state_label = "Working".to_string();
-                        self.started_instant = Some(Instant::now());
-                        self.completed_at = None;
-                        self.overtime_paused_secs = None;
-                        self.paused_work_secs = None;
-                    }
-                    Phase::Break => {
-                        self.status = Status::Running;
-                        self.duration_secs = BREAK_DURATION_SECS;
-                        self.remaining_secs = BREAK_DURATION_SECS;
-                        self.completion_flag = false;
-                        self.state_label = "Break time".to_string();
-                        self.started_instant = Some(Instant::now());
-                        self.completed_at = None;
-                        self.overtime_paused_secs = None;
-                        self.paused_break_secs = None;
-                    }
-                }
-            }
-            Status::BreakReady => {
-                // Start break session
-                self.phase = Phase::Break;
-                self.status = Status::Running;
-                self.duration_secs = BREAK_DURATION_SECS;
-                self.remaining_secs = BREAK_DURATION_SECS;
-                self.completion_flag = false;
-                self.state_label = "Break time".to_string();
-                self.started_instant = Some(Instant::now());
-                self.paused_break_secs = None;
-                // Preserve paused_work_secs for switching back to work later
-            }
-            Status::Running => {
-                return Err("Timer already running".to_string());
-            }
-            Status::Paused => {
-                return Err("Timer is paused, use resume instead".to_string());
-            }
+    pub fn start(&mut self) -> Result<TimerState, TimerError> {
+        self.start_with_tag(None)
+    }
+
+    /// Start like `start`, first setting what the session is spent on. Without
+    /// a tag the phase keeps the one it was last started with.
+    pub fn start_with_tag(&mut self, tag: Option<TaskTag>) -> Result<TimerState, TimerError> {
+        // Phase-aware start: start the current phase from its Ready state, or
+        // restart it after completion (stay in work or break), including a
+        // completion that is due but not yet noticed
+        self.update_remaining();
+        self.check_action(TimerAction::Start)?;
+
+        // Restarting after completion ends the completed session
+        self.finish_session(SessionOutcome::Completed);
+
+        self.status = Status::Running;
+        self.load_duration();
+        self.completion_flag = false;
+        self.state_label = self.phase.running_label().to_string();
+        self.started_instant = Some(self.clock.now());
+        self.completed_at = None;
+        self.session_started_at = Some(self.clock.wall_now());
+        self.paused_at = None;
+        self.overtime_paused = None;
+        // Preserve paused time of other phases for switching back later
+        *self.paused_mut(self.phase) = None;
+        if let Some(tag) = tag {
+            *self.tag_mut(self.phase) = tag;
         }
+        self.begin_session(self.clock.wall_now());
 
         Ok(self.get_state())
     }
 
-    pub fn pause(&mut self) -> Result<TimerState, String> {
-        match self.status {
-            Status::Running => {
-                self.update_remaining();
-                self.status = Status::Paused;
-                match self.phase {
-                    Phase::Work => self.paused_work_secs = Some(self.remaining_secs),
-                    Phase::Break => self.paused_break_secs = Some(self.remaining_secs),
-                }
-                self.started_instant = None;
-                self.state_label = format!(
-                    "Paused ({})",
-                    if self.phase == Phase::Work {
-                        "work"
-                    } else {
-                        "break"
-                    }
-                );
-            }
-            Status::Complete => {
-                let completed_at = self
-                    .completed_at
-                    .ok_or_else(|| "No completion time available".to_string())?;
-                let elapsed = completed_at.elapsed().as_secs() as u32;
-                self.overtime_paused_secs = Some(std::cmp::min(elapsed, 3599));
-                self.status = Status::OvertimePaused;
-                self.state_label = format!(
-                    "Overtime paused ({})",
-                    if self.phase == Phase::Work {
-                        "work"
-                    } else {
-                        "break"
-                    }
-                );
-            }
-            _ => {
-                return Err("No running timer to pause".to_string());
-            }
+    pub fn pause(&mut self) -> Result<TimerState, TimerError> {
+        // A session that ran out meanwhile pauses its overtime instead
+        self.update_remaining();
+        self.check_action(TimerAction::Pause)?;
+        if self.status == Status::Complete {
+            let overtime = self.overtime().ok_or(TimerError::MissingCompletionTime)?;
+            self.pause_overtime(overtime, self.clock.wall_now());
+        } else {
+            self.status = Status::Paused;
+            *self.paused_mut(self.phase) = Some(self.remaining);
+            self.started_instant = None;
+            self.paused_at = Some(self.clock.wall_now());
+            self.state_label = format!("Paused ({})", self.phase.label_name());
+            self.record_pause();
         }
 
         Ok(self.get_state())
     }
 
-    pub fn resume(&mut self) -> Result<TimerState, String> {
-        match self.status {
-            Status::Paused => {
-                self.status = Status::Running;
-                self.started_instant = Some(Instant::now());
-                self.completed_at = None;
-                self.state_label = if self.phase == Phase::Work {
-                    "Working".to_string()
-                } else {
-                    "Break time".to_string()
-                };
-            }
-            Status::OvertimePaused => {
-                let paused_secs = self.overtime_paused_secs.unwrap_or(0);
-                let completed_at = Instant::now()
-                    .checked_sub(std::time::Duration::from_secs(paused_secs as u64))
-                    .unwrap_or_else(Instant::now);
-                self.completed_at = Some(completed_at);
-                self.overtime_paused_secs = None;
-                self.status = Status::Complete;
-                self.state_label = match self.phase {
-                    Phase::Work => "Work completed".to_string(),
-                    Phase::Break => "Break completed".to_string(),
-                };
-            }
-            _ => {
-                return Err("No paused timer to resume".to_string());
+    pub fn resume(&mut self) -> Result<TimerState, TimerError> {
+        self.check_action(TimerAction::Resume)?;
+        if self.status == Status::OvertimePaused {
+            let overtime = self.overtime_paused.unwrap_or_default();
+            self.completed_at = Some(self.instant_ago(overtime));
+            self.overtime_paused = None;
+            self.paused_at = None;
+            self.status = Status::Complete;
+            self.state_label = self.phase.completed_label().to_string();
+        } else {
+            self.record_resume();
+            self.status = Status::Running;
+            self.started_instant = Some(self.clock.now());
+            self.completed_at = None;
+            self.paused_at = None;
+            // A session parked by a phase switch shows as started when resumed
+            if self.session_started_at.is_none() {
+                self.session_started_at = Some(self.clock.wall_now());
             }
+            self.state_label = self.phase.running_label().to_string();
         }
 
         Ok(self.get_state())
     }
 
-    pub fn clear(&mut self) -> Result<TimerState, String> {
+    pub fn clear(&mut self) -> Result<TimerState, TimerError> {
+        self.update_remaining();
+        self.finish_session(SessionOutcome::Cleared);
+
         // Preserve current phase, reset to ready state
-        match self.phase {
-            Phase::Work => {
-                self.status = Status::WorkReady;
-                self.remaining_secs = WORK_DURATION_SECS;
-                self.duration_secs = WORK_DURATION_SECS;
-                self.state_label = "Ready to work".to_string();
-            }
-            Phase::Break => {
-                self.status = Status::BreakReady;
-                self.remaining_secs = BREAK_DURATION_SECS;
-                self.duration_secs = BREAK_DURATION_SECS;
-                self.state_label = "Ready to break".to_string();
-            }
-        }
+        self.status = self.phase.ready_status();
+        self.flow_break_secs = None;
+        self.load_duration();
+        self.state_label = self.phase.ready_label().to_string();
 
         self.completion_flag = false;
         self.started_instant = None;
         self.completed_at = None;
-        self.overtime_paused_secs = None;
-        self.paused_work_secs = None;
-        self.paused_break_secs = None;
+        self.session_started_at = None;
+        self.paused_at = None;
+        self.overtime_paused = None;
+        self.paused_work = None;
+        self.paused_break = None;
+        self.paused_long_break = None;
+        self.work_tag = TaskTag::default();
+        self.break_tag = TaskTag::default();
+        self.long_break_tag = TaskTag::default();
 
         Ok(self.get_state())
     }
 
+    /// End the current session as skipped and move on to the next phase,
+    /// dropping the time left in it. Skipped work does not count toward the
+    /// long break, so it is followed by a short one. The next phase waits in
+    /// its Ready state, or starts right away if the current phase is set to
+    /// auto-start.
+    pub fn skip(&mut self) -> Result<TimerState, TimerError> {
+        self.update_remaining();
+        let action = match self.phase {
+            Phase::Work => self.config.on_work_complete,
+            Phase::Break | Phase::LongBreak => self.config.on_break_complete,
+        };
+        let next = self.phase_after_skip();
+
+        self.finish_session(SessionOutcome::Skipped);
+        *self.paused_mut(self.phase) = None;
+        // The next phase starts fresh, even if it had a parked session
+        *self.paused_mut(next) = None;
+        self.advance_step();
+        self.enter_phase(next);
+
+        if action == CompletionAction::AutoStart {
+            return self.start_with_tag(None);
+        }
+        Ok(self.get_state())
+    }
+
     pub fn set_phase(&mut self, new_phase: Phase) {
         // Idempotent: no-op if already on requested phase
         if new_phase == self.phase {
             return;
         }
 
-        // Clear overtime state when switching phases
-        self.completed_at = None;
+        self.update_remaining();
+        self.finish_session(SessionOutcome::SwitchedAway);
+        self.park_session();
+        self.select_step_for(new_phase);
+        self.enter_phase(new_phase);
+    }
 
+    // Keep the remaining time of a running or paused session in its phase's
+    // slot, so switching back later continues it
+    fn park_session(&mut self) {
         // If currently running, pause and save remaining time
         if self.status == Status::Running {
             self.status = Status::Paused;
             // Store current remaining in exiting phase's field
-            match self.phase {
-                Phase::Work => self.paused_work_secs = Some(self.remaining_secs),
-                Phase::Break => self.paused_break_secs = Some(self.remaining_secs),
-            }
+            *self.paused_mut(self.phase) = Some(self.remaining);
             self.started_instant = None;
         } else if self.status == Status::Paused {
             // Already paused; save current remaining time to exiting phase
-            match self.phase {
-                Phase::Work => self.paused_work_secs = Some(self.remaining_secs),
-                Phase::Break => self.paused_break_secs = Some(self.remaining_secs),
-            }
+            *self.paused_mut(self.phase) = Some(self.remaining);
+        }
+        if self.status == Status::Paused {
+            *self.paused_step_mut(self.phase) = Some(self.step);
         } else if self.status == Status::Complete || self.status == Status::OvertimePaused {
             // Session completed; switching phase clears completion state
             // No need to save remaining time (already 0)
-            // Completion flag will be cleared below
+            // Completion flag will be cleared by enter_phase
         }
+    }
 
-        // Switch to new phase
+    // Switch to `new_phase`, restoring its parked session as Paused or
+    // showing its Ready state. Clears any completion and overtime state.
+    fn enter_phase(&mut self, new_phase: Phase) {
         self.phase = new_phase;
-
-        // Load paused time from new phase, or use standard duration
-        match new_phase {
-            Phase::Work => {
-                self.duration_secs = WORK_DURATION_SECS;
-                self.remaining_secs = self.paused_work_secs.unwrap_or(WORK_DURATION_SECS);
-                // Set status to Paused if we have paused time, otherwise WorkReady
-                if self.paused_work_secs.is_some() {
-                    self.status = Status::Paused;
-                    self.state_label = "Paused (work)".to_string();
-                } else {
-                    self.status = Status::WorkReady;
-                    self.state_label = "Ready to work".to_string();
-                }
-            }
-            Phase::Break => {
-                self.duration_secs = BREAK_DURATION_SECS;
-                self.remaining_secs = self.paused_break_secs.unwrap_or(BREAK_DURATION_SECS);
-                // Set status to Paused if we have paused time, otherwise BreakReady
-                if self.paused_break_secs.is_some() {
-                    self.status = Status::Paused;
-                    self.state_label = "Paused (break)".to_string();
-                } else {
-                    self.status = Status::BreakReady;
-                    self.state_label = "Ready to break".to_string();
-                }
-            }
+        self.started_instant = None;
+        self.completed_at = None;
+        self.session_started_at = None;
+        self.paused_at = None;
+
+        // Load paused time from new phase, or use configured duration.
+        // A parked session may be longer than the configured duration if the
+        // config was shortened meanwhile, so never report less than remaining.
+        let paused = self.paused(new_phase);
+        if new_phase == Phase::Work && paused.is_none() {
+            self.counting_up = self.flowtime_work();
+        }
+        let configured = self.phase_duration(new_phase);
+        self.remaining = paused.unwrap_or(Duration::from_secs(configured.into()));
+        self.duration_secs = if self.counts_up() {
+            0
+        } else {
+            configured.max(self.remaining_secs())
+        };
+        // Set status to Paused if we have paused time, otherwise Ready
+        if paused.is_some() {
+            self.status = Status::Paused;
+            self.state_label = format!("Paused ({})", new_phase.label_name());
+            // The parked session continues as a new one, paused from now on,
+            // so whatever ends or changes it before it is resumed is recorded
+            let now = self.clock.wall_now();
+            self.paused_at = Some(now);
+            self.begin_session(now);
+        } else {
+            self.status = new_phase.ready_status();
+            self.state_label = new_phase.ready_label().to_string();
         }
 
         self.completion_flag = false;
-        self.overtime_paused_secs = None;
+        self.overtime_paused = None;
     }
 }
 
+// Whole seconds in `time`, counting a started second as a full one
+fn secs_rounded_up(time: Duration) -> u32 {
+    time.as_nanos().div_ceil(1_000_000_000) as u32
+}
+
+fn to_rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
 pub type SharedTimerService = Mutex<TimerService>;
 
-pub fn create_timer_service() -> SharedTimerService {
-    Mutex::new(TimerService::new())
+pub fn create_timer_service(snapshot: Option<TimerSnapshot>) -> SharedTimerService {
+    let service = match snapshot {
+        Some(snapshot) => TimerService::restore(snapshot, SystemClock),
+        None => TimerService::new(),
+    };
+    Mutex::new(service)
 }
 
+#[cfg(test)]
+mod proptests;
 #[cfg(test)]
 mod tests;
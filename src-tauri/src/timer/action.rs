@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use super::{OvertimePolicy, Phase, Status, TimerError, TimerService};
+
+/// Timer command, as listed in `TimerState::available_actions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimerAction {
+    Start,
+    Pause,
+    Resume,
+    StopWork,
+    Extend,
+    Shorten,
+    Skip,
+    Clear,
+    Undo,
+    RecordInterruption,
+    SetStep,
+}
+
+impl TimerAction {
+    pub(crate) const ALL: [TimerAction; 11] = [
+        TimerAction::Start,
+        TimerAction::Pause,
+        TimerAction::Resume,
+        TimerAction::StopWork,
+        TimerAction::Extend,
+        TimerAction::Shorten,
+        TimerAction::Skip,
+        TimerAction::Clear,
+        TimerAction::Undo,
+        TimerAction::RecordInterruption,
+        TimerAction::SetStep,
+    ];
+}
+
+impl TimerService {
+    // Whether `action` is accepted in the current state, or the error its
+    // command fails with. The commands check this before changing anything,
+    // so the listed actions are exactly the ones that succeed.
+    pub(crate) fn check_action(&self, action: TimerAction) -> Result<(), TimerError> {
+        let status = self.status;
+        let in_progress = matches!(status, Status::Running | Status::Paused);
+        match action {
+            TimerAction::Start => match status {
+                Status::Running => Err(TimerError::AlreadyRunning),
+                Status::Paused => Err(TimerError::PausedUseResume),
+                _ => Ok(()),
+            },
+            TimerAction::Pause => match status {
+                Status::Running | Status::Complete => Ok(()),
+                _ => Err(TimerError::NotRunning { status }),
+            },
+            TimerAction::Resume => match status {
+                Status::Paused | Status::OvertimePaused => Ok(()),
+                _ => Err(TimerError::NotPaused { status }),
+            },
+            TimerAction::StopWork if self.counts_up() && in_progress => Ok(()),
+            TimerAction::StopWork => Err(TimerError::NotCountingUp {
+                phase: self.phase,
+                status,
+            }),
+            TimerAction::Extend | TimerAction::Shorten
+                if !self.counts_up() && (in_progress || status == Status::Complete) =>
+            {
+                Ok(())
+            }
+            TimerAction::Extend | TimerAction::Shorten => Err(TimerError::NotAdjustable { status }),
+            TimerAction::RecordInterruption if self.phase == Phase::Work && in_progress => Ok(()),
+            TimerAction::RecordInterruption => Err(TimerError::NotInWorkSession {
+                phase: self.phase,
+                status,
+            }),
+            // Whether the step exists is up to `set_step`
+            TimerAction::SetStep if self.program.is_some() => Ok(()),
+            TimerAction::SetStep => Err(TimerError::InvalidStep {
+                step: self.step,
+                step_count: 0,
+            }),
+            TimerAction::Undo if self.can_undo() => Ok(()),
+            TimerAction::Undo => Err(TimerError::NothingToUndo),
+            TimerAction::Skip | TimerAction::Clear => Ok(()),
+        }
+    }
+
+    /// Actions that succeed in the current state, leaving out those that
+    /// would change nothing: clearing a fresh Ready state, shortening a
+    /// completed session that has no time left, or resuming overtime that
+    /// would pause again right away at its cap.
+    pub fn available_actions(&self) -> Vec<TimerAction> {
+        TimerAction::ALL
+            .into_iter()
+            .filter(|&action| self.check_action(action).is_ok() && self.has_effect(action))
+            .collect()
+    }
+
+    /// Available actions that throw away time spent on a session in progress
+    /// or parked in another phase, so they should be confirmed first: clear
+    /// drops all of it, skip the current session's and the next phase's, and
+    /// a jump to another step may drop either.
+    pub fn destructive_actions(&self) -> Vec<TimerAction> {
+        self.available_actions()
+            .into_iter()
+            .filter(|&action| match action {
+                TimerAction::Clear | TimerAction::SetStep => self.has_unfinished_time(),
+                TimerAction::Skip => {
+                    matches!(self.status, Status::Running | Status::Paused)
+                        || self.paused(self.phase_after_skip()).is_some()
+                }
+                _ => false,
+            })
+            .collect()
+    }
+
+    fn has_effect(&self, action: TimerAction) -> bool {
+        match action {
+            TimerAction::Shorten => self.status != Status::Complete,
+            TimerAction::Resume => !matches!(
+                (self.status, self.config.overtime),
+                (Status::OvertimePaused, OvertimePolicy::AutoPause { cap_secs })
+                    if self.overtime_secs() >= Some(cap_secs)
+            ),
+            TimerAction::Clear => {
+                self.status != self.phase.ready_status() || self.has_unfinished_time()
+            }
+            _ => true,
+        }
+    }
+
+    // Whether a session is in progress, or parked to be continued later
+    fn has_unfinished_time(&self) -> bool {
+        matches!(self.status, Status::Running | Status::Paused)
+            || self.paused_work.is_some()
+            || self.paused_break.is_some()
+            || self.paused_long_break.is_some()
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use super::config::{MAX_BREAK_DURATION_SECS, MAX_WORK_DURATION_SECS};
+use super::{Phase, Status, TimerAction, TimerError, TimerService, TimerState, secs_rounded_up};
+
+impl TimerService {
+    /// Add `delta_secs` to the remaining time, or take it away when negative,
+    /// e.g. "+5 minutes" to finish a thought or to cut a break short.
+    ///
+    /// Works on a running, paused or completed countdown. Extending a
+    /// completed session leaves overtime and resumes the countdown; shortening
+    /// a session to nothing completes it right away. The session's duration
+    /// moves with the remaining time, and the change is kept in its history.
+    pub fn adjust_remaining(&mut self, delta_secs: i32) -> Result<TimerState, TimerError> {
+        self.update_remaining();
+        self.check_action(if delta_secs < 0 {
+            TimerAction::Shorten
+        } else {
+            TimerAction::Extend
+        })?;
+
+        let max_secs = match self.phase {
+            Phase::Work => MAX_WORK_DURATION_SECS,
+            Phase::Break | Phase::LongBreak => MAX_BREAK_DURATION_SECS,
+        };
+        // Whole seconds move, the fraction of the current second stays
+        let delta = Duration::from_secs(delta_secs.unsigned_abs().into());
+        let remaining = if delta_secs < 0 {
+            self.remaining.saturating_sub(delta)
+        } else {
+            (self.remaining + delta).min(Duration::from_secs(max_secs.into()))
+        };
+        let applied = secs_rounded_up(remaining) as i64 - self.remaining_secs() as i64;
+        if applied == 0 {
+            return Ok(self.get_state());
+        }
+        self.duration_secs =
+            (self.duration_secs as i64 + applied).max(secs_rounded_up(remaining) as i64) as u32;
+        self.record_adjustment(applied as i32);
+
+        if self.status == Status::Complete {
+            // Back from overtime into the countdown; the session counts as
+            // completed again when it reaches zero
+            if self.phase == Phase::Work {
+                self.completed_work_sessions = self.completed_work_sessions.saturating_sub(1);
+                self.undo_goal_progress();
+            }
+            self.status = Status::Running;
+            self.completion_flag = false;
+            self.completed_at = None;
+            self.state_label = self.phase.running_label().to_string();
+        }
+
+        if remaining.is_zero() {
+            if self.status == Status::Paused {
+                self.record_resume();
+            }
+            self.handle_completion(self.clock.now());
+        } else {
+            // Continue from the new remaining time as if just resumed
+            self.remaining = remaining;
+            *self.paused_mut(self.phase) = Some(remaining);
+            if self.status == Status::Running {
+                self.started_instant = Some(self.clock.now());
+            }
+        }
+
+        Ok(self.get_state())
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{DateTime, FixedOffset, Offset, TimeZone, Utc};
+use std::time::Instant;
+
+use std::time::Duration;
+
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+// Clock that keeps counting while asleep, unlike the one behind `Instant`
+#[cfg(target_os = "linux")]
+const BOOT_CLOCK: libc::clockid_t = libc::CLOCK_BOOTTIME;
+#[cfg(target_os = "macos")]
+const BOOT_CLOCK: libc::clockid_t = libc::CLOCK_MONOTONIC;
+
+/// Source of time for `TimerService`.
+///
+/// Countdown arithmetic uses the monotonic clock, which setting the system
+/// clock does not move; the wall clock is only used for timestamps. Time
+/// spent asleep is found with the boot clock, see `boot_time`.
+pub trait Clock: Send {
+    /// Monotonic time
+    fn now(&self) -> Instant;
+
+    /// Time since boot, including time asleep, or `None` where the platform
+    /// has no such clock
+    fn boot_time(&self) -> Option<Duration>;
+
+    /// Wall-clock time
+    fn wall_now(&self) -> DateTime<Utc>;
+
+    /// Offset of local time from UTC at `time`
+    fn local_offset(&self, time: DateTime<Utc>) -> FixedOffset;
+}
+
+/// Clock backed by the operating system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn boot_time(&self) -> Option<Duration> {
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `time` is a valid timespec for the call to write to
+        if unsafe { libc::clock_gettime(BOOT_CLOCK, &mut time) } != 0 {
+            return None;
+        }
+        Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+    }
+
+    // Elsewhere the monotonic clock already counts time asleep
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn boot_time(&self) -> Option<Duration> {
+        None
+    }
+
+    fn wall_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn local_offset(&self, time: DateTime<Utc>) -> FixedOffset {
+        chrono::Local
+            .offset_from_utc_datetime(&time.naive_utc())
+            .fix()
+    }
+}
+
+/// Clock that only moves when told to, for deterministic tests.
+///
+/// Clones share the same time, so a test can keep one handle and give the
+/// other to the service. Local time is UTC unless set with `with_offset`.
+#[cfg(test)]
+#[derive(Clone)]
+pub struct ManualClock {
+    time: Arc<Mutex<(Instant, Duration, DateTime<Utc>)>>,
+    offset: FixedOffset,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::with_wall_time(Utc::now())
+    }
+
+    /// Clock whose wall-clock time starts at `wall`.
+    pub fn with_wall_time(wall: DateTime<Utc>) -> Self {
+        Self {
+            time: Arc::new(Mutex::new((Instant::now(), Duration::ZERO, wall))),
+            offset: Utc.fix(),
+        }
+    }
+
+    /// Same clock, with local time `offset` from UTC.
+    pub fn with_offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Move all clocks forward.
+    pub fn advance(&self, duration: Duration) {
+        let mut time = self.time.lock().unwrap();
+        time.0 += duration;
+        time.1 += duration;
+        time.2 += duration;
+    }
+
+    /// Move the boot and wall clocks forward but not the monotonic one, as
+    /// when the computer is asleep.
+    pub fn sleep(&self, duration: Duration) {
+        let mut time = self.time.lock().unwrap();
+        time.1 += duration;
+        time.2 += duration;
+    }
+
+    /// Move only the wall clock forward, as when the system clock is set.
+    pub fn set_forward(&self, duration: Duration) {
+        self.time.lock().unwrap().2 += duration;
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.time.lock().unwrap().0
+    }
+
+    fn boot_time(&self) -> Option<Duration> {
+        Some(self.time.lock().unwrap().1)
+    }
+
+    fn wall_now(&self) -> DateTime<Utc> {
+        self.time.lock().unwrap().2
+    }
+
+    fn local_offset(&self, _time: DateTime<Utc>) -> FixedOffset {
+        self.offset
+    }
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    BREAK_DURATION_SECS, DAILY_GOAL, LONG_BREAK_DURATION_SECS, LONG_BREAK_INTERVAL,
+    OVERTIME_CAP_SECS, ROLLOVER_HOUR, TimerError, WORK_DURATION_SECS,
+};
+
+pub(super) const MIN_WORK_DURATION_SECS: u32 = 60; // 1 minute
+pub(super) const MAX_WORK_DURATION_SECS: u32 = 10800; // 3 hours
+pub(super) const MIN_BREAK_DURATION_SECS: u32 = 60; // 1 minute
+pub(super) const MAX_BREAK_DURATION_SECS: u32 = 3600; // 1 hour
+const MAX_LONG_BREAK_INTERVAL: u32 = 12;
+const MAX_DAILY_GOAL: u32 = 48;
+const FLOWTIME_BREAK_DIVISOR: u32 = 5; // A fifth of the time worked
+const MAX_FLOWTIME_BREAK_DIVISOR: u32 = 10;
+const MIN_OVERTIME_LIMIT_SECS: u32 = 60; // 1 minute
+const MAX_OVERTIME_LIMIT_SECS: u32 = 86400; // 24 hours
+const OVERTIME_REMINDER_SECS: u32 = 120; // 2 minutes
+const MIN_OVERTIME_REMINDER_SECS: u32 = 60; // 1 minute
+const MAX_OVERTIME_REMINDER_SECS: u32 = 3600; // 1 hour
+
+/// What happens when a session reaches zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompletionAction {
+    /// Stay in the completed phase and count overtime
+    #[default]
+    Stay,
+    /// Move to the next phase and wait in its Ready state
+    Advance,
+    /// Move to the next phase and start it right away
+    AutoStart,
+}
+
+/// How work sessions are timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimerMode {
+    /// Count down from the configured work duration
+    #[default]
+    Countdown,
+    /// Count up until work is stopped, then take a break in proportion to
+    /// the time worked
+    Flowtime,
+}
+
+/// What a running session does while the computer is asleep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuspendPolicy {
+    /// Keep counting, as if the computer had stayed awake
+    #[default]
+    KeepRunning,
+    /// Pause from the moment the computer went to sleep
+    Pause,
+}
+
+/// What happens to overtime, the time a completed session stays in its
+/// phase, as it grows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum OvertimePolicy {
+    /// Keep counting, but show and record no more than `cap_secs`
+    Cap { cap_secs: u32 },
+    /// Keep counting without limit
+    Uncapped,
+    /// Clear the session, back to the phase's Ready state, once overtime
+    /// reaches `after_secs`
+    AutoClear { after_secs: u32 },
+    /// Pause overtime once it reaches `cap_secs`
+    AutoPause { cap_secs: u32 },
+}
+
+impl Default for OvertimePolicy {
+    fn default() -> Self {
+        OvertimePolicy::Cap {
+            cap_secs: OVERTIME_CAP_SECS,
+        }
+    }
+}
+
+impl OvertimePolicy {
+    /// Most overtime shown or recorded, if limited
+    pub fn limit_secs(self) -> Option<u32> {
+        match self {
+            OvertimePolicy::Cap { cap_secs } | OvertimePolicy::AutoPause { cap_secs } => {
+                Some(cap_secs)
+            }
+            OvertimePolicy::AutoClear { after_secs } => Some(after_secs),
+            OvertimePolicy::Uncapped => None,
+        }
+    }
+}
+
+/// User-configurable durations for each phase, the long break cycle and the
+/// transitions taken on completion.
+///
+/// Changing the configuration never touches a session that is already in
+/// progress: running, paused and completed sessions keep their duration, and
+/// the new values apply from the next session onwards. Only a fresh Ready
+/// state picks up the new duration immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimerConfig {
+    pub work_duration_secs: u32,
+    pub break_duration_secs: u32,
+    pub long_break_duration_secs: u32,
+    /// Number of completed work sessions between long breaks
+    pub long_break_interval: u32,
+    /// Transition taken when a work session completes
+    pub on_work_complete: CompletionAction,
+    /// Transition taken when a break or long break completes
+    pub on_break_complete: CompletionAction,
+    /// Completed work sessions to aim for each day
+    pub daily_goal: u32,
+    /// Local hour at which the daily count starts over
+    pub rollover_hour: u32,
+    pub mode: TimerMode,
+    /// In Flowtime mode a break lasts the time worked divided by this
+    pub flowtime_break_divisor: u32,
+    /// Whether time the computer spends asleep counts toward a running session
+    pub on_suspend: SuspendPolicy,
+    /// Limit on overtime, and what happens when it is reached
+    pub overtime: OvertimePolicy,
+    /// Time between reminders while a completed session is in overtime, or
+    /// None for no reminders
+    pub overtime_reminder_secs: Option<u32>,
+}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            work_duration_secs: WORK_DURATION_SECS,
+            break_duration_secs: BREAK_DURATION_SECS,
+            long_break_duration_secs: LONG_BREAK_DURATION_SECS,
+            long_break_interval: LONG_BREAK_INTERVAL,
+            on_work_complete: CompletionAction::Stay,
+            on_break_complete: CompletionAction::Stay,
+            daily_goal: DAILY_GOAL,
+            rollover_hour: ROLLOVER_HOUR,
+            mode: TimerMode::Countdown,
+            flowtime_break_divisor: FLOWTIME_BREAK_DIVISOR,
+            on_suspend: SuspendPolicy::KeepRunning,
+            overtime: OvertimePolicy::default(),
+            overtime_reminder_secs: Some(OVERTIME_REMINDER_SECS),
+        }
+    }
+}
+
+impl TimerConfig {
+    pub fn validate(&self) -> Result<(), TimerError> {
+        check_range(
+            "workDurationSecs",
+            self.work_duration_secs,
+            MIN_WORK_DURATION_SECS,
+            MAX_WORK_DURATION_SECS,
+        )?;
+        check_range(
+            "breakDurationSecs",
+            self.break_duration_secs,
+            MIN_BREAK_DURATION_SECS,
+            MAX_BREAK_DURATION_SECS,
+        )?;
+        check_range(
+            "longBreakDurationSecs",
+            self.long_break_duration_secs,
+            MIN_BREAK_DURATION_SECS,
+            MAX_BREAK_DURATION_SECS,
+        )?;
+        check_range(
+            "longBreakInterval",
+            self.long_break_interval,
+            1,
+            MAX_LONG_BREAK_INTERVAL,
+        )?;
+        check_range("dailyGoal", self.daily_goal, 1, MAX_DAILY_GOAL)?;
+        check_range("rolloverHour", self.rollover_hour, 0, 23)?;
+        check_range(
+            "flowtimeBreakDivisor",
+            self.flowtime_break_divisor,
+            1,
+            MAX_FLOWTIME_BREAK_DIVISOR,
+        )?;
+        let limit_field = match self.overtime {
+            OvertimePolicy::AutoClear { .. } => "overtime.afterSecs",
+            _ => "overtime.capSecs",
+        };
+        if let Some(limit) = self.overtime.limit_secs() {
+            check_range(
+                limit_field,
+                limit,
+                MIN_OVERTIME_LIMIT_SECS,
+                MAX_OVERTIME_LIMIT_SECS,
+            )?;
+        }
+        if let Some(interval) = self.overtime_reminder_secs {
+            check_range(
+                "overtimeReminderSecs",
+                interval,
+                MIN_OVERTIME_REMINDER_SECS,
+                MAX_OVERTIME_REMINDER_SECS,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// `field` is the camelCase name the frontend uses for the value
+pub(super) fn check_range(
+    field: &'static str,
+    value: u32,
+    min: u32,
+    max: u32,
+) -> Result<(), TimerError> {
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(TimerError::InvalidConfig { field, min, max })
+    }
+}
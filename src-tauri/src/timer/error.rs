@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::fmt;
+
+use super::{Phase, Status};
+
+/// Error returned by `TimerService` and the timer commands.
+///
+/// Serialized with a stable `code` plus the variant's fields, so callers can
+/// branch on the kind of error instead of matching message text, e.g.
+/// `{"code":"notPaused","status":"running"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "code",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum TimerError {
+    /// `start` while a session is already running
+    AlreadyRunning,
+    /// `start` while a session is paused; it must be resumed instead
+    PausedUseResume,
+    /// `pause` without a running or completed session
+    NotRunning { status: Status },
+    /// `resume` without a paused session
+    NotPaused { status: Status },
+    /// A completed session is missing its completion time
+    MissingCompletionTime,
+    /// A config value is outside its allowed range
+    InvalidConfig {
+        field: &'static str,
+        min: u32,
+        max: u32,
+    },
+    /// Unknown phase name passed to `set_phase`
+    InvalidPhase { phase: String },
+    /// `set_step` with an index outside the program, or without a program
+    InvalidStep { step: usize, step_count: usize },
+    /// `adjust_remaining` without a running, paused or completed countdown
+    NotAdjustable { status: Status },
+    /// `undo` with no action left to undo
+    NothingToUndo,
+    /// `stop_work` without a running or paused count-up work session
+    NotCountingUp { phase: Phase, status: Status },
+    /// `record_interruption` outside a running or paused work session
+    NotInWorkSession { phase: Phase, status: Status },
+    /// The timer lock was poisoned by a panic in another thread
+    LockPoisoned,
+    /// The session history file could not be read
+    HistoryUnavailable { reason: String },
+    /// A date range whose start is after its end
+    InvalidDateRange { from: NaiveDate, to: NaiveDate },
+    /// The exported history could not be written
+    ExportFailed { reason: String },
+}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerError::AlreadyRunning => write!(f, "Timer already running"),
+            TimerError::PausedUseResume => write!(f, "Timer is paused, use resume instead"),
+            TimerError::NotRunning { .. } => write!(f, "No running timer to pause"),
+            TimerError::NotPaused { .. } => write!(f, "No paused timer to resume"),
+            TimerError::MissingCompletionTime => write!(f, "No completion time available"),
+            TimerError::InvalidConfig { field, min, max } => {
+                write!(f, "{} must be between {} and {}", field, min, max)
+            }
+            TimerError::InvalidPhase { phase } => write!(
+                f,
+                "Invalid phase '{}'. Use 'work', 'break' or 'longBreak'.",
+                phase
+            ),
+            TimerError::InvalidStep { step, step_count } => write!(
+                f,
+                "Step {} does not exist, the program has {} steps",
+                step, step_count
+            ),
+            TimerError::NotAdjustable { .. } => write!(
+                f,
+                "Only a running, paused or completed countdown can be adjusted"
+            ),
+            TimerError::NothingToUndo => write!(f, "Nothing to undo"),
+            TimerError::NotCountingUp { .. } => write!(
+                f,
+                "Only a running or paused Flowtime work session can be stopped"
+            ),
+            TimerError::NotInWorkSession { .. } => write!(
+                f,
+                "Interruptions can only be recorded during a running or paused work session"
+            ),
+            TimerError::LockPoisoned => write!(f, "Timer state is unavailable"),
+            TimerError::HistoryUnavailable { reason } => {
+                write!(f, "Session history is unavailable: {}", reason)
+            }
+            TimerError::InvalidDateRange { from, to } => {
+                write!(f, "Date range starts at {} after it ends at {}", from, to)
+            }
+            TimerError::ExportFailed { reason } => write!(f, "Export failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for TimerError {}
+
+impl<T> From<std::sync::PoisonError<T>> for TimerError {
+    fn from(_: std::sync::PoisonError<T>) -> Self {
+        TimerError::LockPoisoned
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Serialize;
+
+use super::Phase;
+
+/// Something that happened inside `TimerService` which listeners should hear
+/// about exactly once, even if nobody was reading the state at the time.
+///
+/// Events are queued by the service and drained with
+/// `TimerService::take_events`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum TimerEvent {
+    /// A session reached zero
+    Completed {
+        phase: Phase,
+        /// RFC 3339 time at which the session reached zero
+        completed_at: String,
+    },
+    /// The day's completed work sessions reached the daily goal
+    GoalReached {
+        completed_today: u32,
+        daily_goal: u32,
+        /// RFC 3339 time of the completion that reached the goal
+        reached_at: String,
+    },
+    /// A completed session is still in overtime, sent every
+    /// `TimerConfig::overtime_reminder_secs` until it is started again,
+    /// cleared or left. Paused overtime sends none.
+    OvertimeReminder {
+        phase: Phase,
+        /// 1 for the first reminder after completion, one more for each
+        /// interval since
+        level: u32,
+        overtime_secs: u32,
+    },
+}
+
+impl TimerEvent {
+    /// Name of the Tauri event the frontend listens to
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimerEvent::Completed { .. } => "timer-completed",
+            TimerEvent::GoalReached { .. } => "timer-goal-reached",
+            TimerEvent::OvertimeReminder { .. } => "timer-overtime-reminder",
+        }
+    }
+}
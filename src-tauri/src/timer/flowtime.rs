@@ -0,0 +1,56 @@
+use super::config::{MAX_BREAK_DURATION_SECS, MIN_BREAK_DURATION_SECS};
+use super::{
+    CompletionAction, Phase, SessionOutcome, TimerAction, TimerError, TimerEvent, TimerMode,
+    TimerService, TimerState, to_rfc3339,
+};
+
+impl TimerService {
+    /// End a count-up work session, which has no end of its own. The session
+    /// counts as completed, and the next break is loaded with a length in
+    /// proportion to the time worked. It starts right away if work sessions
+    /// are configured to auto-start the next phase.
+    pub fn stop_work(&mut self) -> Result<TimerState, TimerError> {
+        self.update_remaining();
+        self.check_action(TimerAction::StopWork)?;
+
+        let stopped_at = self.clock.wall_now();
+        let worked_secs = self.remaining_secs();
+        let next = self.next_phase();
+        self.finish_session(SessionOutcome::Completed);
+        self.paused_work = None;
+        self.completed_work_sessions += 1;
+        self.events.push(TimerEvent::Completed {
+            phase: Phase::Work,
+            completed_at: to_rfc3339(stopped_at),
+        });
+        self.record_goal_progress(stopped_at);
+
+        // The computed break replaces any break parked earlier
+        self.flow_break_secs = Some(self.flow_break_secs_for(worked_secs));
+        *self.paused_mut(next) = None;
+        self.enter_phase(next);
+        self.completion_flag = true;
+
+        if self.config.on_work_complete == CompletionAction::AutoStart {
+            return self.start_with_tag(None);
+        }
+        Ok(self.get_state())
+    }
+
+    /// Whether the current session counts up instead of down.
+    pub fn counts_up(&self) -> bool {
+        self.phase == Phase::Work && self.counting_up
+    }
+
+    // Whether a fresh work session counts up. A program's steps always have
+    // their own duration, so it takes precedence over Flowtime.
+    pub(crate) fn flowtime_work(&self) -> bool {
+        self.config.mode == TimerMode::Flowtime && self.program.is_none()
+    }
+
+    // Break earned by `worked_secs` of work, within the allowed break range
+    fn flow_break_secs_for(&self, worked_secs: u32) -> u32 {
+        (worked_secs / self.config.flowtime_break_divisor)
+            .clamp(MIN_BREAK_DURATION_SECS, MAX_BREAK_DURATION_SECS)
+    }
+}
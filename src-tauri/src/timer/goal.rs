@@ -0,0 +1,49 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::{TimerEvent, TimerService, to_rfc3339};
+
+impl TimerService {
+    // Count a completed work session towards the goal of its day, announcing
+    // the goal the first time the count reaches it. A session that is
+    // extended and completes again counts once, and announces nothing new.
+    pub(crate) fn record_goal_progress(&mut self, completed_at: DateTime<Utc>) {
+        let day = self.goal_day_of(completed_at);
+        if self.goal_day != Some(day) {
+            self.goal_day = Some(day);
+            self.completed_today = 0;
+            self.goal_announced = false;
+        }
+        self.completed_today += 1;
+
+        if !self.goal_announced && self.completed_today >= self.config.daily_goal {
+            self.goal_announced = true;
+            self.events.push(TimerEvent::GoalReached {
+                completed_today: self.completed_today,
+                daily_goal: self.config.daily_goal,
+                reached_at: to_rfc3339(completed_at),
+            });
+        }
+    }
+
+    // Take back the count of a completed work session that was extended
+    pub(crate) fn undo_goal_progress(&mut self) {
+        self.completed_today = self.completed_today.saturating_sub(1);
+    }
+
+    /// Work sessions completed since the last rollover; zero once a new day
+    /// has begun, even before anything is completed in it.
+    pub fn completed_today(&self) -> u32 {
+        if self.goal_day == Some(self.goal_day_of(self.clock.wall_now())) {
+            self.completed_today
+        } else {
+            0
+        }
+    }
+
+    // Local date a time counts towards. Times before the rollover hour still
+    // belong to the previous day, so a late session counts for the evening.
+    fn goal_day_of(&self, time: DateTime<Utc>) -> NaiveDate {
+        let local = time.with_timezone(&self.clock.local_offset(time));
+        (local - chrono::Duration::hours(self.config.rollover_hour as i64)).date_naive()
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use super::{OvertimePolicy, SessionOutcome, Status, TimerEvent, TimerService};
+
+impl TimerService {
+    // Overtime of a completed session, limited as the overtime policy says.
+    // Everything that shows, pauses or records overtime goes through here.
+    pub(crate) fn overtime(&self) -> Option<Duration> {
+        match self.status {
+            Status::Complete => self.completed_at.map(|completed| {
+                let elapsed = self.elapsed_since(completed);
+                match self.overtime_limit() {
+                    Some(limit) => elapsed.min(limit),
+                    None => elapsed,
+                }
+            }),
+            Status::OvertimePaused => self.overtime_paused,
+            _ => None,
+        }
+    }
+
+    pub(crate) fn overtime_secs(&self) -> Option<u32> {
+        self.overtime().map(|overtime| overtime.as_secs() as u32)
+    }
+
+    pub(crate) fn pause_overtime(&mut self, overtime: Duration, paused_at: DateTime<Utc>) {
+        self.overtime_paused = Some(overtime);
+        self.status = Status::OvertimePaused;
+        self.paused_at = Some(paused_at);
+        self.state_label = format!("Overtime paused ({})", self.phase.label_name());
+    }
+
+    // Clear or pause a completed session whose overtime reached the limit,
+    // as of the moment it was reached
+    pub(crate) fn apply_overtime_policy(&mut self) {
+        let (Status::Complete, Some(completed_at), Some(limit)) =
+            (self.status, self.completed_at, self.overtime_limit())
+        else {
+            return;
+        };
+        if self.elapsed_since(completed_at) < limit {
+            return;
+        }
+
+        match self.config.overtime {
+            OvertimePolicy::AutoPause { .. } => {
+                let reached_at = self.wall_of(completed_at + limit);
+                self.pause_overtime(limit, reached_at);
+            }
+            OvertimePolicy::AutoClear { .. } => {
+                // Only this session ends; time parked in other phases and
+                // the task tags stay
+                self.finish_session(SessionOutcome::Completed);
+                self.status = self.phase.ready_status();
+                self.load_duration();
+                self.state_label = self.phase.ready_label().to_string();
+                self.completion_flag = false;
+                self.completed_at = None;
+                self.session_started_at = None;
+                self.paused_at = None;
+            }
+            OvertimePolicy::Cap { .. } | OvertimePolicy::Uncapped => {}
+        }
+    }
+
+    // Queue a reminder each time another interval of overtime has passed.
+    // Intervals that passed unobserved, e.g. while asleep, give a single
+    // reminder at the level reached rather than one each.
+    pub(crate) fn remind_of_overtime(&mut self) {
+        let (Status::Complete, Some(completed_at), Some(interval)) = (
+            self.status,
+            self.completed_at,
+            self.config.overtime_reminder_secs,
+        ) else {
+            return;
+        };
+        let level = (self.elapsed_since(completed_at).as_secs() / u64::from(interval)) as u32;
+        if level > self.overtime_reminders {
+            self.overtime_reminders = level;
+            self.events.push(TimerEvent::OvertimeReminder {
+                phase: self.phase,
+                level,
+                overtime_secs: self.overtime_secs().unwrap_or(0),
+            });
+        }
+    }
+
+    fn overtime_limit(&self) -> Option<Duration> {
+        self.config
+            .overtime
+            .limit_secs()
+            .map(|secs| Duration::from_secs(secs.into()))
+    }
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use super::config::{
+    MAX_BREAK_DURATION_SECS, MAX_WORK_DURATION_SECS, MIN_BREAK_DURATION_SECS,
+    MIN_WORK_DURATION_SECS, check_range,
+};
+use super::{Phase, SessionOutcome, TimerError, TimerService, TimerState};
+
+const MAX_PROGRAM_STEPS: u32 = 32;
+
+/// One step of a program: a phase run for its own duration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramStep {
+    pub phase: Phase,
+    pub duration_secs: u32,
+    /// Optional display name, e.g. "Warm-up"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl ProgramStep {
+    fn validate(&self) -> Result<(), TimerError> {
+        match self.phase {
+            Phase::Work => check_range(
+                "durationSecs",
+                self.duration_secs,
+                MIN_WORK_DURATION_SECS,
+                MAX_WORK_DURATION_SECS,
+            ),
+            Phase::Break | Phase::LongBreak => check_range(
+                "durationSecs",
+                self.duration_secs,
+                MIN_BREAK_DURATION_SECS,
+                MAX_BREAK_DURATION_SECS,
+            ),
+        }
+    }
+}
+
+/// Named sequence of steps the timer walks through instead of alternating
+/// work and breaks, e.g. "Warm-up 15, Work 50, Break 10, Work 50, Long break
+/// 30". After the last step the program starts over.
+///
+/// Each step uses its own duration; the configured phase durations and the
+/// long break interval only apply without a program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Program {
+    pub name: String,
+    pub steps: Vec<ProgramStep>,
+}
+
+impl Program {
+    pub fn validate(&self) -> Result<(), TimerError> {
+        check_range("steps", self.steps.len() as u32, 1, MAX_PROGRAM_STEPS)?;
+        self.steps.iter().try_for_each(ProgramStep::validate)
+    }
+}
+
+impl TimerService {
+    pub fn program(&self) -> Option<&Program> {
+        self.program.as_ref()
+    }
+
+    /// Follow `program`, or the default alternation with `None`. Like a config
+    /// change, this never touches a session in progress: a fresh Ready state
+    /// moves to the first step right away, otherwise the program carries on
+    /// from its first step in the current phase.
+    pub fn set_program(&mut self, program: Option<Program>) -> Result<TimerState, TimerError> {
+        if let Some(program) = &program {
+            program.validate()?;
+        }
+        self.program = program;
+        self.step = 0;
+
+        if self.status == self.phase.ready_status() {
+            let first = self.current_step().map_or(self.phase, |step| step.phase);
+            self.enter_phase(first);
+        } else {
+            self.step = self.first_step_in_phase();
+        }
+
+        Ok(self.get_state())
+    }
+
+    /// Jump to a step of the program. The session being left is parked as
+    /// with `set_phase` if the step is in another phase; in the same phase
+    /// there is nowhere to park it, so it ends as skipped and its time is
+    /// dropped. The step continues the time it parked itself, if any, and
+    /// otherwise starts from its full duration in its Ready state; time
+    /// parked in its phase by another step is dropped.
+    pub fn set_step(&mut self, step: usize) -> Result<TimerState, TimerError> {
+        let Some(target) = self.program_step(step).map(|target| target.phase) else {
+            return Err(TimerError::InvalidStep {
+                step,
+                step_count: self.program.as_ref().map_or(0, |p| p.steps.len()),
+            });
+        };
+        // Idempotent like `set_phase`
+        if step == self.step && target == self.phase {
+            return Ok(self.get_state());
+        }
+
+        self.update_remaining();
+        if target == self.phase {
+            self.finish_session(SessionOutcome::Skipped);
+            *self.paused_mut(target) = None;
+        } else {
+            self.finish_session(SessionOutcome::SwitchedAway);
+            self.park_session();
+            if self.paused_step(target) != Some(step) {
+                *self.paused_mut(target) = None;
+            }
+        }
+        self.step = step;
+        self.enter_phase(target);
+
+        Ok(self.get_state())
+    }
+
+    pub(crate) fn current_step(&self) -> Option<&ProgramStep> {
+        self.program_step(self.step)
+    }
+
+    pub(crate) fn next_step(&self) -> Option<&ProgramStep> {
+        let program = self.program.as_ref()?;
+        program.steps.get((self.step + 1) % program.steps.len())
+    }
+
+    // Move on to the next step, wrapping around after the last one
+    pub(crate) fn advance_step(&mut self) {
+        if let Some(program) = &self.program {
+            self.step = (self.step + 1) % program.steps.len();
+        }
+    }
+
+    // Move to the step that continues in `phase`: the one its paused time
+    // was parked from, or else the first step in `phase` after the current
+    // one, wrapping around. A phase the program does not use leaves the step
+    // unchanged.
+    pub(crate) fn select_step_for(&mut self, phase: Phase) {
+        let Some(program) = &self.program else {
+            return;
+        };
+        if let Some(step) = self.paused_step(phase).filter(|&step| {
+            program
+                .steps
+                .get(step)
+                .is_some_and(|step| step.phase == phase)
+        }) {
+            self.step = step;
+            return;
+        }
+        let count = program.steps.len();
+        if let Some(step) = (1..=count)
+            .map(|offset| (self.step + offset) % count)
+            .find(|&index| program.steps[index].phase == phase)
+        {
+            self.step = step;
+        }
+    }
+
+    // First step of the program in the current phase, or the first step if
+    // the program has none in it
+    pub(crate) fn first_step_in_phase(&self) -> usize {
+        self.program
+            .as_ref()
+            .and_then(|program| {
+                program
+                    .steps
+                    .iter()
+                    .position(|step| step.phase == self.phase)
+            })
+            .unwrap_or(0)
+    }
+
+    fn program_step(&self, step: usize) -> Option<&ProgramStep> {
+        self.program.as_ref()?.steps.get(step)
+    }
+}
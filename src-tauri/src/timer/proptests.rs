@@ -0,0 +1,310 @@
+//! Property tests running random sequences of timer commands against a
+//! simple model of the state machine
+
+use super::clock::ManualClock;
+use super::*;
+use proptest::prelude::*;
+
+const WORK_SECS: u32 = 180;
+const BREAK_SECS: u32 = 60;
+const LONG_BREAK_SECS: u32 = 120;
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Start,
+    Pause,
+    Resume,
+    Clear,
+    SetPhase(Phase),
+    /// Let time pass. With `tick` the state is read afterwards, as the
+    /// ticker does every second; without, the next command is the first to
+    /// notice the time that passed.
+    Advance {
+        millis: u64,
+        tick: bool,
+    },
+}
+
+fn phase() -> impl Strategy<Value = Phase> {
+    prop_oneof![
+        Just(Phase::Work),
+        Just(Phase::Break),
+        Just(Phase::LongBreak)
+    ]
+}
+
+// Operations other than letting time pass
+fn command() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        3 => Just(Operation::Start),
+        2 => Just(Operation::Pause),
+        2 => Just(Operation::Resume),
+        1 => Just(Operation::Clear),
+        3 => phase().prop_map(Operation::SetPhase),
+    ]
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        11 => command(),
+        // Mostly steps within a session, sometimes far into overtime
+        4 => (0u64..=200_000, any::<bool>())
+            .prop_map(|(millis, tick)| Operation::Advance { millis, tick }),
+        1 => (200_000u64..=5_000_000, any::<bool>())
+            .prop_map(|(millis, tick)| Operation::Advance { millis, tick }),
+    ]
+}
+
+fn configured_secs(phase: Phase) -> u32 {
+    match phase {
+        Phase::Work => WORK_SECS,
+        Phase::Break => BREAK_SECS,
+        Phase::LongBreak => LONG_BREAK_SECS,
+    }
+}
+
+fn configured_millis(phase: Phase) -> u64 {
+    configured_secs(phase) as u64 * 1000
+}
+
+fn slot(phase: Phase) -> usize {
+    match phase {
+        Phase::Work => 0,
+        Phase::Break => 1,
+        Phase::LongBreak => 2,
+    }
+}
+
+/// What the timer should show, tracked the plain way: counters of
+/// milliseconds that time moves directly, instead of instants and elapsed
+/// time.
+#[derive(Debug, Clone)]
+struct Model {
+    phase: Phase,
+    status: Status,
+    remaining_millis: u64,
+    /// Time since the session completed, capped only when paused
+    overtime_millis: u64,
+    /// Remaining time of sessions parked by switching phase
+    parked: [Option<u64>; 3],
+    completed_work_sessions: u32,
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            phase: Phase::Work,
+            status: Status::WorkReady,
+            remaining_millis: configured_millis(Phase::Work),
+            overtime_millis: 0,
+            parked: [None; 3],
+            completed_work_sessions: 0,
+        }
+    }
+
+    // Apply an operation, returning whether the command should succeed
+    fn apply(&mut self, operation: Operation) -> bool {
+        match operation {
+            Operation::Start => {
+                if matches!(self.status, Status::Running | Status::Paused) {
+                    return false;
+                }
+                self.status = Status::Running;
+                self.remaining_millis = configured_millis(self.phase);
+                self.overtime_millis = 0;
+                self.parked[slot(self.phase)] = None;
+            }
+            Operation::Pause => match self.status {
+                Status::Running => self.status = Status::Paused,
+                Status::Complete => {
+                    self.status = Status::OvertimePaused;
+                    self.overtime_millis =
+                        self.overtime_millis.min(OVERTIME_CAP_SECS as u64 * 1000);
+                }
+                _ => return false,
+            },
+            Operation::Resume => match self.status {
+                Status::Paused => self.status = Status::Running,
+                Status::OvertimePaused => self.status = Status::Complete,
+                _ => return false,
+            },
+            Operation::Clear => {
+                self.status = self.phase.ready_status();
+                self.remaining_millis = configured_millis(self.phase);
+                self.overtime_millis = 0;
+                self.parked = [None; 3];
+            }
+            Operation::SetPhase(phase) => {
+                if phase != self.phase {
+                    if matches!(self.status, Status::Running | Status::Paused) {
+                        self.parked[slot(self.phase)] = Some(self.remaining_millis);
+                    }
+                    self.phase = phase;
+                    self.overtime_millis = 0;
+                    match self.parked[slot(phase)] {
+                        Some(remaining) => {
+                            self.status = Status::Paused;
+                            self.remaining_millis = remaining;
+                        }
+                        None => {
+                            self.status = phase.ready_status();
+                            self.remaining_millis = configured_millis(phase);
+                        }
+                    }
+                }
+            }
+            Operation::Advance { millis, .. } => match self.status {
+                Status::Running if millis >= self.remaining_millis => {
+                    self.overtime_millis = millis - self.remaining_millis;
+                    self.remaining_millis = 0;
+                    self.status = Status::Complete;
+                    self.parked[slot(self.phase)] = None;
+                    if self.phase == Phase::Work {
+                        self.completed_work_sessions += 1;
+                    }
+                }
+                Status::Running => self.remaining_millis -= millis,
+                Status::Complete => self.overtime_millis += millis,
+                _ => {}
+            },
+        }
+        true
+    }
+
+    fn shown_overtime_secs(&self) -> Option<u32> {
+        matches!(self.status, Status::Complete | Status::OvertimePaused)
+            .then(|| ((self.overtime_millis / 1000) as u32).min(OVERTIME_CAP_SECS))
+    }
+}
+
+fn new_service() -> (TimerService, ManualClock) {
+    let clock = ManualClock::new();
+    let mut service = TimerService::with_clock(clock.clone());
+    service
+        .set_config(TimerConfig {
+            work_duration_secs: WORK_SECS,
+            break_duration_secs: BREAK_SECS,
+            long_break_duration_secs: LONG_BREAK_SECS,
+            ..TimerConfig::default()
+        })
+        .unwrap();
+    (service, clock)
+}
+
+// Run an operation, returning whether the command succeeded
+fn run(service: &mut TimerService, clock: &ManualClock, operation: Operation) -> bool {
+    match operation {
+        Operation::Start => service.start().is_ok(),
+        Operation::Pause => service.pause().is_ok(),
+        Operation::Resume => service.resume().is_ok(),
+        Operation::Clear => service.clear().is_ok(),
+        Operation::SetPhase(phase) => {
+            service.set_phase(phase);
+            true
+        }
+        Operation::Advance { millis, tick } => {
+            clock.advance(Duration::from_millis(millis));
+            if tick {
+                service.get_state();
+            }
+            true
+        }
+    }
+}
+
+// Invariants that hold in every state, whatever led to it
+fn check_invariants(service: &TimerService, state: &TimerState) -> Result<(), TestCaseError> {
+    prop_assert!(state.remaining_secs <= state.duration_secs);
+    prop_assert_eq!(
+        state.overtime_secs.is_some(),
+        matches!(state.status, Status::Complete | Status::OvertimePaused)
+    );
+    prop_assert!(state.overtime_secs.unwrap_or(0) <= OVERTIME_CAP_SECS);
+    prop_assert_eq!(
+        state.overtime_paused_secs.is_some(),
+        state.status == Status::OvertimePaused
+    );
+    prop_assert_eq!(
+        service.started_instant.is_some(),
+        state.status == Status::Running
+    );
+    // May be kept while overtime is paused, until resuming replaces it
+    if state.status == Status::Complete {
+        prop_assert!(service.completed_at.is_some());
+    } else if state.status != Status::OvertimePaused {
+        prop_assert!(service.completed_at.is_none());
+    }
+    prop_assert_eq!(state.ends_at.is_some(), state.status == Status::Running);
+    prop_assert_eq!(
+        state.completion_flag,
+        matches!(state.status, Status::Complete | Status::OvertimePaused)
+    );
+    if state.status == state.phase.ready_status() {
+        prop_assert_eq!(state.remaining_secs, state.duration_secs);
+    }
+    Ok(())
+}
+
+fn check_against_model(state: &TimerState, model: &Model) -> Result<(), TestCaseError> {
+    prop_assert_eq!(state.phase, model.phase);
+    prop_assert_eq!(state.status, model.status);
+    prop_assert_eq!(state.remaining_millis, model.remaining_millis);
+    // Counting a started second as a full one
+    prop_assert_eq!(
+        state.remaining_secs as u64,
+        model.remaining_millis.div_ceil(1000)
+    );
+    prop_assert_eq!(state.duration_secs, configured_secs(model.phase));
+    prop_assert_eq!(state.overtime_secs, model.shown_overtime_secs());
+    prop_assert_eq!(state.completed_work_sessions, model.completed_work_sessions);
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn prop_timer_follows_model(operations in prop::collection::vec(operation(), 1..80)) {
+        let (mut service, clock) = new_service();
+        let mut model = Model::new();
+
+        for operation in operations {
+            let expected = model.apply(operation);
+            prop_assert_eq!(run(&mut service, &clock, operation), expected, "{:?}", operation);
+
+            // Time that passed unobserved is checked by the next command
+            if let Operation::Advance { tick: false, .. } = operation {
+                continue;
+            }
+            let state = service.get_state();
+            check_invariants(&service, &state)?;
+            check_against_model(&state, &model)?;
+        }
+    }
+
+    #[test]
+    fn prop_undo_restores_previous_state(
+        operations in prop::collection::vec(operation(), 0..40),
+        // Undo does not turn back time, only commands
+        last in command(),
+    ) {
+        let (mut service, clock) = new_service();
+        for operation in operations {
+            run(&mut service, &clock, operation);
+        }
+        let before = service.get_state();
+
+        let done = service
+            .undoable(|service| {
+                run(service, &clock, last)
+                    .then_some(())
+                    .ok_or(TimerError::NothingToUndo)
+            })
+            .is_ok();
+        if done {
+            service.undo().unwrap();
+        }
+
+        prop_assert_eq!(service.get_state(), before, "{:?}", last);
+    }
+}
@@ -0,0 +1,354 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Phase, Status, TimerAction, TimerError, TimerService, TimerState};
+
+/// How a session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionOutcome {
+    /// Ran down to zero
+    Completed,
+    /// Cleared before reaching zero
+    Cleared,
+    /// Left for another phase before reaching zero; the remaining time stays
+    /// parked and continues as a new session when resumed
+    SwitchedAway,
+    /// Skipped to the next phase before reaching zero; the remaining time is
+    /// dropped
+    Skipped,
+}
+
+/// What a session is spent on. Both parts are optional free text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTag {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl TaskTag {
+    /// Trim both parts, treating blank text as absent
+    pub fn new(task: Option<String>, project: Option<String>) -> Self {
+        Self {
+            task: clean_text(task),
+            project: clean_text(project),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.task.is_none() && self.project.is_none()
+    }
+}
+
+/// Source of an interruption, as counted by the Pomodoro Technique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InterruptionKind {
+    /// Self-inflicted, e.g. the urge to check mail
+    Internal,
+    /// Caused by someone or something else, e.g. a phone call
+    External,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interruption {
+    pub kind: InterruptionKind,
+    pub at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A change to the remaining time of a session in progress.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Adjustment {
+    pub at: DateTime<Utc>,
+    /// Seconds added to the remaining time, negative when shortened. This is
+    /// the change actually applied after limits, not the one requested.
+    pub delta_secs: i32,
+}
+
+/// Time the computer was asleep during a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suspension {
+    /// When the computer went to sleep, as closely as the timer can tell
+    pub at: DateTime<Utc>,
+    pub secs: u32,
+    /// Whether the time asleep counted toward the session; otherwise the
+    /// session was paused for it
+    pub counted: bool,
+}
+
+/// One finished session in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRecord {
+    pub phase: Phase,
+    pub outcome: SessionOutcome,
+    pub started_at: DateTime<Utc>,
+    /// When the countdown reached zero, or when the session was abandoned.
+    /// Overtime after completion is counted separately.
+    pub ended_at: DateTime<Utc>,
+    pub planned_secs: u32,
+    /// Time actually spent counting down, excluding pauses
+    pub focused_secs: u32,
+    pub pause_count: u32,
+    pub paused_secs: u32,
+    pub overtime_secs: u32,
+    #[serde(flatten)]
+    pub tag: TaskTag,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interruptions: Vec<Interruption>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adjustments: Vec<Adjustment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suspensions: Vec<Suspension>,
+}
+
+impl SessionRecord {
+    pub fn interruption_count(&self, kind: InterruptionKind) -> u32 {
+        count_of(&self.interruptions, kind)
+    }
+
+    /// Record of a session of `phase` that ran its planned `secs` from
+    /// `started_at` without pauses, overtime or anything noted. Tests set
+    /// the fields they care about on top with struct update syntax.
+    #[cfg(test)]
+    pub(crate) fn sample(
+        phase: Phase,
+        outcome: SessionOutcome,
+        started_at: DateTime<Utc>,
+        secs: u32,
+    ) -> Self {
+        Self {
+            phase,
+            outcome,
+            started_at,
+            ended_at: started_at + chrono::Duration::seconds(secs.into()),
+            planned_secs: secs,
+            focused_secs: secs,
+            pause_count: 0,
+            paused_secs: 0,
+            overtime_secs: 0,
+            tag: TaskTag::default(),
+            interruptions: Vec::new(),
+            adjustments: Vec::new(),
+            suspensions: Vec::new(),
+        }
+    }
+
+    // Whether no time was focused and nothing was noted
+    fn is_empty(&self) -> bool {
+        self.focused_secs == 0
+            && self.interruptions.is_empty()
+            && self.adjustments.is_empty()
+            && self.suspensions.is_empty()
+    }
+}
+
+/// Bookkeeping for the session in progress, kept until it ends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSession {
+    pub phase: Phase,
+    pub started_at: DateTime<Utc>,
+    pub planned_secs: u32,
+    /// Remaining time when the session started; less than planned for a
+    /// parked session that is continued
+    pub initial_secs: u32,
+    pub pause_count: u32,
+    /// Paused time up to the last resume
+    pub paused_secs: u32,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub tag: TaskTag,
+    pub interruptions: Vec<Interruption>,
+    pub adjustments: Vec<Adjustment>,
+    pub suspensions: Vec<Suspension>,
+}
+
+impl ActiveSession {
+    // Total change to the remaining time so far
+    fn adjusted_secs(&self) -> i64 {
+        self.adjustments
+            .iter()
+            .map(|adjustment| adjustment.delta_secs as i64)
+            .sum()
+    }
+}
+
+impl TimerService {
+    /// Note an interruption of the current work session. Only a running or
+    /// paused work session can be interrupted; a blank note is dropped.
+    pub fn record_interruption(
+        &mut self,
+        kind: InterruptionKind,
+        note: Option<String>,
+    ) -> Result<TimerState, TimerError> {
+        self.update_remaining();
+        self.check_action(TimerAction::RecordInterruption)?;
+
+        let interruption = Interruption {
+            kind,
+            at: self.clock.wall_now(),
+            note: clean_text(note),
+        };
+        // Every session in progress is tracked, a parked one included
+        let (phase, status) = (self.phase, self.status);
+        let session = self
+            .session
+            .as_mut()
+            .ok_or(TimerError::NotInWorkSession { phase, status })?;
+        session.interruptions.push(interruption);
+        Ok(self.get_state())
+    }
+
+    // Interruptions of the current session so far
+    pub(crate) fn interruption_count(&self, kind: InterruptionKind) -> u32 {
+        self.session
+            .as_ref()
+            .map_or(0, |session| count_of(&session.interruptions, kind))
+    }
+
+    /// Drain the sessions that ended since the last call, oldest first.
+    pub fn take_finished_sessions(&mut self) -> Vec<SessionRecord> {
+        std::mem::take(&mut self.finished_sessions)
+    }
+
+    // Start tracking the current phase's session from its current countdown
+    pub(crate) fn begin_session(&mut self, started_at: DateTime<Utc>) {
+        // A break earned by Flowtime work is used up once the break begins,
+        // and forfeited once more work begins instead
+        self.flow_break_secs = None;
+        self.session = Some(ActiveSession {
+            phase: self.phase,
+            started_at,
+            planned_secs: self.duration_secs,
+            initial_secs: self.remaining_secs(),
+            pause_count: 0,
+            paused_secs: 0,
+            completed_at: None,
+            tag: self.tag(self.phase).clone(),
+            interruptions: Vec::new(),
+            adjustments: Vec::new(),
+            suspensions: Vec::new(),
+        });
+    }
+
+    pub(crate) fn record_pause(&mut self) {
+        if let Some(session) = self.session.as_mut() {
+            session.pause_count += 1;
+        }
+    }
+
+    pub(crate) fn record_resume(&mut self) {
+        let paused = self.paused_so_far();
+        if let Some(session) = self.session.as_mut() {
+            session.paused_secs += paused;
+        }
+    }
+
+    pub(crate) fn record_completion(&mut self, completed_at: DateTime<Utc>) {
+        if let Some(session) = self.session.as_mut() {
+            session.completed_at = Some(completed_at);
+        }
+    }
+
+    pub(crate) fn record_adjustment(&mut self, delta_secs: i32) {
+        let at = self.clock.wall_now();
+        if let Some(session) = self.session.as_mut() {
+            session.adjustments.push(Adjustment { at, delta_secs });
+            // An extended session is no longer complete
+            if delta_secs > 0 {
+                session.completed_at = None;
+            }
+        }
+    }
+
+    pub(crate) fn record_suspension(&mut self, suspension: Suspension) {
+        if let Some(session) = self.session.as_mut() {
+            session.suspensions.push(suspension);
+        }
+    }
+
+    /// End the tracked session, if any. A session that already reached zero
+    /// always counts as completed, whatever ended it afterwards.
+    pub(crate) fn finish_session(&mut self, outcome: SessionOutcome) {
+        let Some(session) = self.session.take() else {
+            return;
+        };
+
+        let record = match session.completed_at {
+            Some(completed_at) => SessionRecord {
+                phase: session.phase,
+                outcome: SessionOutcome::Completed,
+                started_at: session.started_at,
+                ended_at: completed_at,
+                planned_secs: session.planned_secs,
+                focused_secs: (session.initial_secs as i64 + session.adjusted_secs()).max(0) as u32,
+                pause_count: session.pause_count,
+                paused_secs: session.paused_secs,
+                overtime_secs: self.overtime_secs().unwrap_or(0),
+                tag: session.tag,
+                interruptions: session.interruptions,
+                adjustments: session.adjustments,
+                suspensions: session.suspensions,
+            },
+            None => SessionRecord {
+                phase: session.phase,
+                outcome,
+                started_at: session.started_at,
+                ended_at: self.clock.wall_now(),
+                planned_secs: session.planned_secs,
+                focused_secs: if self.counts_up() {
+                    self.remaining_secs().saturating_sub(session.initial_secs)
+                } else {
+                    (session.initial_secs as i64 + session.adjusted_secs()
+                        - self.remaining_secs() as i64)
+                        .max(0) as u32
+                },
+                pause_count: session.pause_count,
+                paused_secs: session.paused_secs + self.paused_so_far(),
+                overtime_secs: 0,
+                tag: session.tag,
+                interruptions: session.interruptions,
+                adjustments: session.adjustments,
+                suspensions: session.suspensions,
+            },
+        };
+        // A parked session left again before anything happened to it simply
+        // stays parked, with nothing worth a history entry
+        if record.outcome == SessionOutcome::SwitchedAway && record.is_empty() {
+            return;
+        }
+        self.finished_sessions.push(record);
+    }
+
+    // Length of the current countdown pause, zero unless paused
+    fn paused_so_far(&self) -> u32 {
+        match (self.status, self.paused_at) {
+            (Status::Paused, Some(paused_at)) => (self.clock.wall_now() - paused_at)
+                .to_std()
+                .unwrap_or_default()
+                .as_secs() as u32,
+            _ => 0,
+        }
+    }
+}
+
+// Trimmed text, or None if blank
+fn clean_text(text: Option<String>) -> Option<String> {
+    text.map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn count_of(interruptions: &[Interruption], kind: InterruptionKind) -> u32 {
+    interruptions
+        .iter()
+        .filter(|interruption| interruption.kind == kind)
+        .count() as u32
+}
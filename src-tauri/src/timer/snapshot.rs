@@ -0,0 +1,186 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{ActiveSession, Clock, Phase, Program, Status, TaskTag, TimerConfig, TimerService};
+
+/// Serializable copy of the full `TimerService` state.
+///
+/// Monotonic instants do not survive a restart, so running and completed
+/// sessions are stored as wall-clock timestamps and converted back relative
+/// to the current time when restored. Times are kept to the millisecond.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerSnapshot {
+    pub saved_at: DateTime<Utc>,
+    pub phase: Phase,
+    pub status: Status,
+    /// Time left, or the time counted so far while counting up
+    pub remaining_millis: u64,
+    pub duration_secs: u32,
+    pub counting_up: bool,
+    pub flow_break_secs: Option<u32>,
+    pub completion_flag: bool,
+    /// Start of the current running stretch, i.e. the last start or resume
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub session_started_at: Option<DateTime<Utc>>,
+    pub paused_at: Option<DateTime<Utc>>,
+    pub overtime_paused_millis: Option<u64>,
+    pub overtime_reminders: u32,
+    pub paused_work_millis: Option<u64>,
+    pub paused_break_millis: Option<u64>,
+    pub paused_long_break_millis: Option<u64>,
+    pub paused_work_step: Option<usize>,
+    pub paused_break_step: Option<usize>,
+    pub paused_long_break_step: Option<usize>,
+    pub work_tag: TaskTag,
+    pub break_tag: TaskTag,
+    pub long_break_tag: TaskTag,
+    pub completed_work_sessions: u32,
+    pub goal_day: Option<NaiveDate>,
+    pub completed_today: u32,
+    pub goal_announced: bool,
+    pub state_label: String,
+    pub config: TimerConfig,
+    pub program: Option<Program>,
+    pub step: usize,
+    pub session: Option<ActiveSession>,
+}
+
+impl TimerService {
+    pub fn snapshot(&self) -> TimerSnapshot {
+        TimerSnapshot {
+            saved_at: self.clock.wall_now(),
+            phase: self.phase,
+            status: self.status,
+            remaining_millis: self.remaining.as_millis() as u64,
+            duration_secs: self.duration_secs,
+            counting_up: self.counting_up,
+            flow_break_secs: self.flow_break_secs,
+            completion_flag: self.completion_flag,
+            started_at: self.started_instant.map(|instant| self.wall_of(instant)),
+            completed_at: self.completed_at.map(|instant| self.wall_of(instant)),
+            session_started_at: self.session_started_at,
+            paused_at: self.paused_at,
+            overtime_paused_millis: self.overtime_paused.map(as_millis),
+            overtime_reminders: self.overtime_reminders,
+            paused_work_millis: self.paused_work.map(as_millis),
+            paused_break_millis: self.paused_break.map(as_millis),
+            paused_long_break_millis: self.paused_long_break.map(as_millis),
+            paused_work_step: self.paused_work_step,
+            paused_break_step: self.paused_break_step,
+            paused_long_break_step: self.paused_long_break_step,
+            work_tag: self.work_tag.clone(),
+            break_tag: self.break_tag.clone(),
+            long_break_tag: self.long_break_tag.clone(),
+            completed_work_sessions: self.completed_work_sessions,
+            goal_day: self.goal_day,
+            completed_today: self.completed_today,
+            goal_announced: self.goal_announced,
+            state_label: self.state_label.clone(),
+            config: self.config,
+            program: self.program.clone(),
+            step: self.step,
+            session: self.session.clone(),
+        }
+    }
+
+    /// Rebuild a service from a snapshot, counting the wall-clock time that
+    /// passed since it was taken. A session that ran out while the app was
+    /// closed is completed as if the app had been open.
+    pub fn restore(snapshot: TimerSnapshot, clock: impl Clock + 'static) -> Self {
+        let mut service = Self::with_clock(clock);
+        service.load_snapshot(snapshot);
+        service
+    }
+
+    // Replace the timer state with a snapshot's, as `restore` describes.
+    // Queued events, finished sessions and undo history are kept.
+    pub(crate) fn load_snapshot(&mut self, snapshot: TimerSnapshot) {
+        let wall_now = self.clock.wall_now();
+        // Wall-clock time since a timestamp; zero if the clock went backwards
+        let since = |time: DateTime<Utc>| (wall_now - time).to_std().unwrap_or_default();
+
+        // Monotonic instants are rebuilt below where they apply
+        self.started_instant = None;
+        self.completed_at = None;
+
+        self.phase = snapshot.phase;
+        self.status = snapshot.status;
+        self.remaining = Duration::from_millis(snapshot.remaining_millis);
+        self.duration_secs = snapshot.duration_secs;
+        self.counting_up = snapshot.counting_up;
+        self.flow_break_secs = snapshot.flow_break_secs;
+        self.completion_flag = snapshot.completion_flag;
+        self.session_started_at = snapshot.session_started_at;
+        self.paused_at = snapshot.paused_at;
+        self.overtime_paused = snapshot.overtime_paused_millis.map(Duration::from_millis);
+        self.overtime_reminders = snapshot.overtime_reminders;
+        self.paused_work = snapshot.paused_work_millis.map(Duration::from_millis);
+        self.paused_break = snapshot.paused_break_millis.map(Duration::from_millis);
+        self.paused_long_break = snapshot.paused_long_break_millis.map(Duration::from_millis);
+        self.paused_work_step = snapshot.paused_work_step;
+        self.paused_break_step = snapshot.paused_break_step;
+        self.paused_long_break_step = snapshot.paused_long_break_step;
+        self.work_tag = snapshot.work_tag;
+        self.break_tag = snapshot.break_tag;
+        self.long_break_tag = snapshot.long_break_tag;
+        self.completed_work_sessions = snapshot.completed_work_sessions;
+        self.goal_day = snapshot.goal_day;
+        self.completed_today = snapshot.completed_today;
+        self.goal_announced = snapshot.goal_announced;
+        self.state_label = snapshot.state_label;
+        // A damaged or hand-edited file must not leave values the timer
+        // divides by, such as the long break interval, at zero
+        self.config = snapshot.config;
+        if let Err(e) = self.config.validate() {
+            log::warn!("Ignoring saved timer config: {}", e);
+            self.config = TimerConfig::default();
+        }
+        self.program = snapshot.program.filter(|program| match program.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Ignoring saved program '{}': {}", program.name, e);
+                false
+            }
+        });
+        self.step = snapshot.step;
+        if self.current_step().is_none() {
+            self.step = self.first_step_in_phase();
+        }
+        self.session = snapshot.session;
+
+        match (self.status, snapshot.started_at, snapshot.completed_at) {
+            (Status::Running, Some(started_at), _) => {
+                // The elapsed time may exceed the system uptime after a
+                // reboot, so the session continues from its remaining time as
+                // if it had just been resumed instead of backdating an instant
+                let elapsed = since(started_at);
+                let initial = self.initial_remaining();
+                if self.counts_up() {
+                    let worked = initial.saturating_add(elapsed);
+                    *self.paused_mut(self.phase) = Some(worked);
+                    self.remaining = worked;
+                    self.started_instant = Some(self.clock.now());
+                } else if elapsed >= initial {
+                    let overrun = elapsed - initial;
+                    self.handle_completion(self.instant_ago(overrun));
+                    self.update_remaining();
+                } else {
+                    *self.paused_mut(self.phase) = Some(initial - elapsed);
+                    self.remaining = initial - elapsed;
+                    self.started_instant = Some(self.clock.now());
+                }
+            }
+            (Status::Complete, _, Some(completed_at)) => {
+                self.completed_at = Some(self.instant_ago(since(completed_at)));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn as_millis(time: Duration) -> u64 {
+    time.as_millis() as u64
+}
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use super::{Status, SuspendPolicy, Suspension, TimerService};
+
+// Clocks drifting apart by more than this between two updates means the
+// computer was asleep; less is scheduling delay or a clock correction
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(5);
+
+impl TimerService {
+    // Look for a suspend since the last update. The monotonic clock stops
+    // while the computer sleeps but the boot clock does not, so the
+    // difference between the two is the time spent asleep. A session running
+    // or in overtime then counts that time or pauses for it, as configured,
+    // and keeps a record of it.
+    //
+    // Setting the wall clock moves neither, so it is not taken for a
+    // suspend. Where there is no boot clock the monotonic one keeps going
+    // during sleep, no gap shows, and the time asleep always counts.
+    pub(crate) fn detect_suspend(&mut self) {
+        let Some(boot_time) = self.clock.boot_time() else {
+            return;
+        };
+        let now = (self.clock.now(), boot_time, self.clock.wall_now());
+        let Some((last_instant, last_boot_time, last_wall)) = self.last_check.replace(now) else {
+            return;
+        };
+        let awake = now.0.saturating_duration_since(last_instant);
+        let passed = now.1.saturating_sub(last_boot_time);
+        let asleep = passed.saturating_sub(awake);
+        if asleep < SUSPEND_THRESHOLD || !matches!(self.status, Status::Running | Status::Complete)
+        {
+            return;
+        }
+
+        // The computer went to sleep at most one update after the last one
+        let counted = self.config.on_suspend == SuspendPolicy::KeepRunning;
+        self.record_suspension(Suspension {
+            at: last_wall,
+            secs: asleep.as_secs() as u32,
+            counted,
+        });
+        if counted {
+            // Move the start of the countdown or overtime back, as if the
+            // monotonic clock had kept going
+            self.started_instant = self.started_instant.map(|start| earlier(start, asleep));
+            self.completed_at = self.completed_at.map(|done| earlier(done, asleep));
+        } else if self.pause().is_ok() {
+            // The monotonic clock already left out the time asleep; only the
+            // pause itself goes back to when the computer went to sleep
+            self.paused_at = Some(last_wall);
+        }
+    }
+}
+
+// `instant` moved back by `duration`, or unchanged if that is out of range
+fn earlier(instant: Instant, duration: Duration) -> Instant {
+    instant.checked_sub(duration).unwrap_or(instant)
+}
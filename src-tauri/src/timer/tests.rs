@@ -1,23 +1,25 @@
 //! Unit tests for TimerService state machine and behavior
 
+use super::clock::ManualClock;
 use super::*;
-use std::thread::sleep;
 use std::time::Duration;
 
-// Helper: simulate elapsed time without real waiting by backdating started_instant
-fn fast_forward(service: &mut TimerService, seconds: u64) {
-    service.started_instant = Some(Instant::now() - Duration::from_secs(seconds));
-    service.update_remaining();
+// Helper: service driven by a manual clock, so time only moves when a test says so
+fn new_service() -> (TimerService, ManualClock) {
+    let clock = ManualClock::new();
+    (TimerService::with_clock(clock.clone()), clock)
 }
 
-fn complete_work_session(service: &mut TimerService) {
-    service.duration_secs = WORK_DURATION_SECS;
-    fast_forward(service, WORK_DURATION_SECS as u64 + 1);
+// Helper: simulate elapsed time without real waiting
+fn fast_forward(service: &mut TimerService, clock: &ManualClock, seconds: u64) {
+    clock.advance(Duration::from_secs(seconds));
+    service.update_remaining();
 }
 
-fn complete_break_session(service: &mut TimerService) {
-    service.duration_secs = BREAK_DURATION_SECS;
-    fast_forward(service, BREAK_DURATION_SECS as u64 + 1);
+// Helper: run the current session down to exactly zero
+fn complete_session(service: &mut TimerService, clock: &ManualClock) {
+    let remaining = service.get_state().remaining_secs;
+    fast_forward(service, clock, remaining as u64);
 }
 
 #[test]
@@ -47,9 +49,9 @@ fn test_start_begins_work_timer() {
 
 #[test]
 fn test_start_while_running_does_nothing() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
-    sleep(Duration::from_millis(100));
+    clock.advance(Duration::from_millis(100));
 
     // Try to start again - should return error
     let result = service.start();
@@ -63,9 +65,9 @@ fn test_start_while_running_does_nothing() {
 
 #[test]
 fn test_pause_freezes_countdown() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
-    sleep(Duration::from_millis(200));
+    clock.advance(Duration::from_millis(200));
 
     service.pause().unwrap();
     let paused_state = service.get_state();
@@ -73,7 +75,7 @@ fn test_pause_freezes_countdown() {
     let remaining_at_pause = paused_state.remaining_secs;
 
     // Wait and verify time doesn't decrease
-    sleep(Duration::from_millis(200));
+    clock.advance(Duration::from_millis(200));
     let still_paused = service.get_state();
     assert_eq!(still_paused.remaining_secs, remaining_at_pause);
     assert_eq!(still_paused.state_label, "Paused (work)");
@@ -81,14 +83,14 @@ fn test_pause_freezes_countdown() {
 
 #[test]
 fn test_resume_continues_from_paused_time() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
-    sleep(Duration::from_millis(200));
+    clock.advance(Duration::from_millis(200));
 
     service.pause().unwrap();
     let remaining_at_pause = service.get_state().remaining_secs;
 
-    sleep(Duration::from_millis(100));
+    clock.advance(Duration::from_millis(100));
     service.resume().unwrap();
 
     let resumed = service.get_state();
@@ -99,14 +101,14 @@ fn test_resume_continues_from_paused_time() {
 
 #[test]
 fn test_pause_resume_cycle_preserves_time() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Multiple pause/resume cycles
     for _ in 0..3 {
-        sleep(Duration::from_millis(50));
+        clock.advance(Duration::from_millis(50));
         service.pause().unwrap();
-        sleep(Duration::from_millis(50));
+        clock.advance(Duration::from_millis(50));
         service.resume().unwrap();
     }
 
@@ -118,9 +120,9 @@ fn test_pause_resume_cycle_preserves_time() {
 
 #[test]
 fn test_clear_from_running_state() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
-    sleep(Duration::from_millis(100));
+    clock.advance(Duration::from_millis(100));
 
     service.clear().unwrap();
 
@@ -134,9 +136,9 @@ fn test_clear_from_running_state() {
 
 #[test]
 fn test_clear_from_paused_state() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
-    sleep(Duration::from_millis(100));
+    clock.advance(Duration::from_millis(100));
     service.pause().unwrap();
 
     service.clear().unwrap();
@@ -148,17 +150,15 @@ fn test_clear_from_paused_state() {
 
 #[test]
 fn test_pause_resume_in_break_phase() {
-    let mut service = TimerService::new();
-    service.phase = Phase::Break;
-    service.status = Status::Running;
-    service.remaining_secs = BREAK_DURATION_SECS;
-    service.started_instant = Some(Instant::now());
+    let (mut service, clock) = new_service();
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
 
-    sleep(Duration::from_millis(200));
+    clock.advance(Duration::from_millis(200));
     service.pause().unwrap();
     let paused_remaining = service.get_state().remaining_secs;
 
-    sleep(Duration::from_millis(200));
+    clock.advance(Duration::from_millis(200));
     service.resume().unwrap();
 
     let resumed = service.get_state();
@@ -170,10 +170,8 @@ fn test_pause_resume_in_break_phase() {
 #[test]
 fn test_clear_during_break() {
     let mut service = TimerService::new();
-    service.phase = Phase::Break;
-    service.status = Status::Running;
-    service.remaining_secs = BREAK_DURATION_SECS;
-    service.started_instant = Some(Instant::now());
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
 
     service.clear().unwrap();
 
@@ -185,13 +183,11 @@ fn test_clear_during_break() {
 
 #[test]
 fn test_completion_flag_resets_on_next_cycle() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
-    // Complete work session (simulate)
-    service.phase = Phase::Work;
-    service.status = Status::Running;
-    service.remaining_secs = 0;
-    service.handle_completion(Instant::now());
+    // Complete work session
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
 
     assert!(service.get_state().completion_flag);
 
@@ -202,7 +198,7 @@ fn test_completion_flag_resets_on_next_cycle() {
 
 #[test]
 fn test_state_labels_match_phase_and_status() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Idle work
     assert_eq!(service.get_state().state_label, "Ready to work");
@@ -215,25 +211,23 @@ fn test_state_labels_match_phase_and_status() {
     service.pause().unwrap();
     assert_eq!(service.get_state().state_label, "Paused (work)");
 
-    // Running break (simulate)
-    service.phase = Phase::Break;
-    service.status = Status::Running;
-    service.state_label = "Break time".to_string();
+    // Running break
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
     assert_eq!(service.get_state().state_label, "Break time");
 
     // Complete
-    service.status = Status::Complete;
-    service.state_label = "Break complete".to_string();
-    assert_eq!(service.get_state().state_label, "Break complete");
+    complete_session(&mut service, &clock);
+    assert_eq!(service.get_state().state_label, "Break completed");
 }
 
 #[test]
 fn test_timing_accuracy_within_tolerance() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Run for approximately 1 second
-    sleep(Duration::from_millis(1000));
+    clock.advance(Duration::from_millis(1000));
     service.update_remaining();
 
     let state = service.get_state();
@@ -278,11 +272,11 @@ fn test_clear_resets_to_initial_state() {
 
 #[test]
 fn test_monotonic_timing_handles_system_clock_changes() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     let instant1 = service.started_instant.unwrap();
-    sleep(Duration::from_millis(100));
+    clock.advance(Duration::from_millis(100));
 
     // Verify we're using Instant (monotonic) not SystemTime
     let instant2 = service.started_instant.unwrap();
@@ -297,14 +291,11 @@ fn test_monotonic_timing_handles_system_clock_changes() {
 
 #[test]
 fn test_work_completion_stays_in_work_mode() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Simulate completion of work session
-    service.duration_secs = WORK_DURATION_SECS;
-    service.started_instant =
-        Some(Instant::now() - Duration::from_secs(WORK_DURATION_SECS as u64 + 1));
-    service.update_remaining();
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 + 1);
 
     let state = service.get_state();
     assert_eq!(state.phase, Phase::Work);
@@ -316,14 +307,11 @@ fn test_work_completion_stays_in_work_mode() {
 
 #[test]
 fn test_start_after_work_completion_restarts_work() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Simulate completion of work session
-    service.duration_secs = WORK_DURATION_SECS;
-    service.started_instant =
-        Some(Instant::now() - Duration::from_secs(WORK_DURATION_SECS as u64 + 1));
-    service.update_remaining();
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 + 1);
 
     // Start should restart work session from Complete status
     service.start().unwrap();
@@ -337,14 +325,14 @@ fn test_start_after_work_completion_restarts_work() {
 
 #[test]
 fn test_completion_flag_set_on_work_completion() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Before completion
     assert!(!service.completion_flag);
 
     // After completion (simulated)
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
 
     assert!(service.completion_flag);
@@ -354,17 +342,14 @@ fn test_completion_flag_set_on_work_completion() {
 
 #[test]
 fn test_break_completion_stays_in_break_mode() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Switch to break phase and start
     service.set_phase(Phase::Break);
     service.start().unwrap();
 
     // Simulate completion of break session
-    service.duration_secs = BREAK_DURATION_SECS;
-    service.started_instant =
-        Some(Instant::now() - Duration::from_secs(BREAK_DURATION_SECS as u64 + 1));
-    service.update_remaining();
+    fast_forward(&mut service, &clock, BREAK_DURATION_SECS as u64 + 1);
 
     let state = service.get_state();
     assert_eq!(state.phase, Phase::Break);
@@ -376,17 +361,14 @@ fn test_break_completion_stays_in_break_mode() {
 
 #[test]
 fn test_start_after_break_completion_restarts_break() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Switch to break phase and start
     service.set_phase(Phase::Break);
     service.start().unwrap();
 
     // Simulate completion of break session
-    service.duration_secs = BREAK_DURATION_SECS;
-    service.started_instant =
-        Some(Instant::now() - Duration::from_secs(BREAK_DURATION_SECS as u64 + 1));
-    service.update_remaining();
+    fast_forward(&mut service, &clock, BREAK_DURATION_SECS as u64 + 1);
 
     // Start should restart break session from Complete status
     service.start().unwrap();
@@ -402,11 +384,11 @@ fn test_start_after_break_completion_restarts_break() {
 
 #[test]
 fn test_phase_switch_from_complete_status() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Complete work session
     service.start().unwrap();
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
 
     // Verify in Complete status
@@ -425,11 +407,11 @@ fn test_phase_switch_from_complete_status() {
 
 #[test]
 fn test_same_phase_switch_after_complete_resets() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Complete work session
     service.start().unwrap();
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
 
     assert_eq!(service.status, Status::Complete);
@@ -445,11 +427,11 @@ fn test_same_phase_switch_after_complete_resets() {
 
 #[test]
 fn test_clear_preserves_current_phase() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Complete work session
     service.start().unwrap();
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
 
     assert_eq!(service.phase, Phase::Work);
@@ -469,24 +451,24 @@ fn test_clear_preserves_current_phase() {
 
 #[test]
 fn test_work_countdown_continues_after_resume() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session and restart
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
     service.start().unwrap();
 
     // Simulate 1 second into session, then pause
-    fast_forward(&mut service, 1);
+    fast_forward(&mut service, &clock, 1);
     service.pause().unwrap();
-    let remaining_at_pause = service.remaining_secs;
+    let remaining_at_pause = service.remaining_secs();
 
     // While paused, time should not progress (no fast-forward)
     service.resume().unwrap();
 
     // Simulate 1 second of running after resume
-    fast_forward(&mut service, 1);
+    fast_forward(&mut service, &clock, 1);
     let state = service.get_state();
 
     assert!(state.remaining_secs < remaining_at_pause);
@@ -495,11 +477,11 @@ fn test_work_countdown_continues_after_resume() {
 
 #[test]
 fn test_completion_flag_set_on_completion_after_restart() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
     // Restart same work session
     service.start().unwrap();
@@ -508,7 +490,7 @@ fn test_completion_flag_set_on_completion_after_restart() {
     assert!(!service.completion_flag);
 
     // Complete work session again
-    complete_break_session(&mut service);
+    complete_session(&mut service, &clock);
     service.get_state();
 
     // Flag should be set to trigger chime
@@ -519,11 +501,11 @@ fn test_completion_flag_set_on_completion_after_restart() {
 
 #[test]
 fn test_pause_at_one_second_then_complete_stays_in_phase() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Fast forward to 00:01 (1 second remaining)
-    fast_forward(&mut service, WORK_DURATION_SECS as u64 - 1);
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 - 1);
     service.pause().unwrap();
 
     let paused_state = service.get_state();
@@ -532,7 +514,7 @@ fn test_pause_at_one_second_then_complete_stays_in_phase() {
 
     // Resume and let it complete
     service.resume().unwrap();
-    fast_forward(&mut service, 2); // Go past completion
+    fast_forward(&mut service, &clock, 2); // Go past completion
 
     let completed_state = service.get_state();
     assert_eq!(completed_state.phase, Phase::Work);
@@ -541,6 +523,49 @@ fn test_pause_at_one_second_then_complete_stays_in_phase() {
     assert!(completed_state.completion_flag);
 }
 
+#[test]
+fn test_completed_session_does_not_return_as_paused() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    service.pause().unwrap();
+    service.resume().unwrap();
+    complete_session(&mut service, &clock);
+
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    let state = service.get_state();
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+}
+
+#[test]
+fn test_pause_after_unobserved_completion_pauses_overtime() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    // Time passes without the state being read in between
+    clock.advance(Duration::from_secs(WORK_DURATION_SECS as u64 + 30));
+
+    let state = service.pause().unwrap();
+
+    assert_eq!(state.status, Status::OvertimePaused);
+    assert_eq!(state.overtime_paused_secs, Some(30));
+    assert_eq!(state.completed_work_sessions, 1);
+}
+
+#[test]
+fn test_start_after_unobserved_completion_restarts() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    clock.advance(Duration::from_secs(WORK_DURATION_SECS as u64));
+
+    let state = service.start().unwrap();
+
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+    assert_eq!(state.completed_work_sessions, 1);
+}
+
 #[test]
 fn test_start_while_running_returns_error() {
     let mut service = TimerService::new();
@@ -549,8 +574,50 @@ fn test_start_while_running_returns_error() {
     // Try to start again while running
     let result = service.start();
 
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Timer already running");
+    let error = result.unwrap_err();
+    assert_eq!(error, TimerError::AlreadyRunning);
+    assert_eq!(error.to_string(), "Timer already running");
+}
+
+#[test]
+fn test_start_while_paused_returns_error() {
+    let mut service = TimerService::new();
+    service.start().unwrap();
+    service.pause().unwrap();
+
+    assert_eq!(service.start().unwrap_err(), TimerError::PausedUseResume);
+}
+
+#[test]
+fn test_resume_error_reports_current_status() {
+    let mut service = TimerService::new();
+    service.start().unwrap();
+
+    let error = service.resume().unwrap_err();
+
+    assert_eq!(
+        error,
+        TimerError::NotPaused {
+            status: Status::Running
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({ "code": "notPaused", "status": "running" })
+    );
+}
+
+#[test]
+fn test_pause_error_serializes_with_code() {
+    let mut service = TimerService::new();
+
+    let error = service.pause().unwrap_err();
+
+    assert_eq!(error.to_string(), "No running timer to pause");
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({ "code": "notRunning", "status": "workReady" })
+    );
 }
 
 // ===== Tests for set_phase() method =====
@@ -558,24 +625,24 @@ fn test_start_while_running_returns_error() {
 #[test]
 fn test_set_phase_idempotent() {
     let mut service = TimerService::new();
-    let initial_remaining = service.remaining_secs;
+    let initial_remaining = service.remaining_secs();
 
     // Call set_phase with same phase
     service.set_phase(Phase::Work);
 
     // Should not change state
     assert_eq!(service.phase, Phase::Work);
-    assert_eq!(service.remaining_secs, initial_remaining);
+    assert_eq!(service.remaining_secs(), initial_remaining);
     assert_eq!(service.status, Status::WorkReady);
 }
 
 #[test]
 fn test_set_phase_preserves_paused_time() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Start work, fast forward 5 minutes, pause it
     service.start().unwrap();
-    fast_forward(&mut service, 300);
+    fast_forward(&mut service, &clock, 300);
     service.pause().unwrap();
     let work_paused_secs = service.get_state().remaining_secs;
     assert!(work_paused_secs < WORK_DURATION_SECS);
@@ -583,22 +650,25 @@ fn test_set_phase_preserves_paused_time() {
     // Switch to break
     service.set_phase(Phase::Break);
     assert_eq!(service.phase, Phase::Break);
-    assert_eq!(service.remaining_secs, BREAK_DURATION_SECS);
+    assert_eq!(service.remaining_secs(), BREAK_DURATION_SECS);
     assert_eq!(service.status, Status::BreakReady);
-    assert_eq!(service.paused_work_secs, Some(work_paused_secs));
+    assert_eq!(
+        service.paused_work,
+        Some(Duration::from_secs(work_paused_secs.into()))
+    );
 
     // Switch back to work
     service.set_phase(Phase::Work);
     assert_eq!(service.phase, Phase::Work);
-    assert_eq!(service.remaining_secs, work_paused_secs); // Restored
+    assert_eq!(service.remaining_secs(), work_paused_secs); // Restored
     assert_eq!(service.status, Status::Paused);
 }
 
 #[test]
 fn test_set_phase_pauses_running_timer() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
-    fast_forward(&mut service, 300); // 5 minutes
+    fast_forward(&mut service, &clock, 300); // 5 minutes
 
     // Still running, capture remaining
     let work_state = service.get_state();
@@ -612,67 +682,82 @@ fn test_set_phase_pauses_running_timer() {
     // Work should be paused and saved
     assert_eq!(service.phase, Phase::Break);
     assert_eq!(service.status, Status::BreakReady);
-    assert_eq!(service.paused_work_secs, Some(remaining_at_switch));
+    assert_eq!(
+        service.paused_work,
+        Some(Duration::from_secs(remaining_at_switch.into()))
+    );
 
     // Break should show standard duration
-    assert_eq!(service.remaining_secs, BREAK_DURATION_SECS);
+    assert_eq!(service.remaining_secs(), BREAK_DURATION_SECS);
 }
 
 #[test]
 fn test_set_phase_loads_standard_duration() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Start and pause work after 5 minutes
     service.start().unwrap();
-    fast_forward(&mut service, 300);
+    fast_forward(&mut service, &clock, 300);
     service.pause().unwrap();
-    assert!(service.remaining_secs < WORK_DURATION_SECS);
+    assert!(service.remaining_secs() < WORK_DURATION_SECS);
 
     // Switch to break - should load standard break duration
     service.set_phase(Phase::Break);
-    assert_eq!(service.remaining_secs, BREAK_DURATION_SECS);
+    assert_eq!(service.remaining_secs(), BREAK_DURATION_SECS);
     assert_eq!(service.duration_secs, BREAK_DURATION_SECS);
 
     // Switch to work - should restore paused work time
     service.set_phase(Phase::Work);
-    assert!(service.remaining_secs < WORK_DURATION_SECS);
-    assert!(service.remaining_secs > 0);
+    assert!(service.remaining_secs() < WORK_DURATION_SECS);
+    assert!(service.remaining_secs() > 0);
 }
 
 #[test]
 fn test_set_phase_paused_to_paused_preserves_both() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
 
     // Pause work at 20:00 (after 5 min)
     service.start().unwrap();
-    fast_forward(&mut service, 300);
+    fast_forward(&mut service, &clock, 300);
     service.pause().unwrap();
     let work_remaining = service.get_state().remaining_secs;
-    assert_eq!(service.paused_work_secs, Some(work_remaining)); // Verify saved
+    assert_eq!(
+        service.paused_work,
+        Some(Duration::from_secs(work_remaining.into()))
+    ); // Verify saved
 
     // Switch to break, start and pause at 4:00 (after 1 min)
     service.set_phase(Phase::Break);
     assert_eq!(service.phase, Phase::Break);
-    // paused_work_secs should still be saved from work phase
-    assert_eq!(service.paused_work_secs, Some(work_remaining));
+    // paused_work should still be saved from work phase
+    assert_eq!(
+        service.paused_work,
+        Some(Duration::from_secs(work_remaining.into()))
+    );
 
     service.start().unwrap();
-    fast_forward(&mut service, 60);
+    fast_forward(&mut service, &clock, 60);
     service.pause().unwrap();
     let break_remaining = service.get_state().remaining_secs;
 
     // Verify both are saved
-    assert_eq!(service.paused_work_secs, Some(work_remaining));
-    assert_eq!(service.paused_break_secs, Some(break_remaining));
+    assert_eq!(
+        service.paused_work,
+        Some(Duration::from_secs(work_remaining.into()))
+    );
+    assert_eq!(
+        service.paused_break,
+        Some(Duration::from_secs(break_remaining.into()))
+    );
 
     // Switch back to work - should see work remaining
     service.set_phase(Phase::Work);
-    assert_eq!(service.remaining_secs, work_remaining);
+    assert_eq!(service.remaining_secs(), work_remaining);
     assert_eq!(service.phase, Phase::Work);
 
     // Switch to break - should see break remaining
     service.set_phase(Phase::Break);
-    assert_eq!(service.remaining_secs, break_remaining);
+    assert_eq!(service.remaining_secs(), break_remaining);
     assert_eq!(service.phase, Phase::Break);
 }
 
@@ -680,15 +765,14 @@ fn test_set_phase_paused_to_paused_preserves_both() {
 
 #[test]
 fn test_overtime_displayed_after_work_completion() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session and wait 10 seconds
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to exactly 10 seconds ago
-    let now = Instant::now();
-    service.completed_at = Some(now - Duration::from_secs(10));
+    // Let exactly 10 seconds of overtime pass
+    clock.advance(Duration::from_secs(10));
 
     let state = service.get_state();
     assert_eq!(state.status, Status::Complete);
@@ -697,17 +781,13 @@ fn test_overtime_displayed_after_work_completion() {
 
 #[test]
 fn test_overtime_caps_at_59_59() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session and wait 2 hours (7200 seconds)
-    complete_work_session(&mut service);
-    sleep(Duration::from_millis(100));
-
-    // Fast-forward the completed_at timestamp by 7200 seconds
-    if let Some(completed) = service.completed_at {
-        service.completed_at = Some(completed - Duration::from_secs(7200));
-    }
+    complete_session(&mut service, &clock);
+    clock.advance(Duration::from_millis(100));
+    clock.advance(Duration::from_secs(7200));
 
     let state = service.get_state();
     assert_eq!(state.status, Status::Complete);
@@ -716,15 +796,14 @@ fn test_overtime_caps_at_59_59() {
 
 #[test]
 fn test_overtime_cleared_on_start() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session with overtime
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to exactly 30 seconds ago
-    let now = Instant::now();
-    service.completed_at = Some(now - Duration::from_secs(30));
+    // Let exactly 30 seconds of overtime pass
+    clock.advance(Duration::from_secs(30));
 
     // Verify overtime exists
     assert_eq!(service.get_state().overtime_secs, Some(30));
@@ -740,15 +819,14 @@ fn test_overtime_cleared_on_start() {
 
 #[test]
 fn test_overtime_cleared_on_clear() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session with overtime
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to exactly 45 seconds ago
-    let now = Instant::now();
-    service.completed_at = Some(now - Duration::from_secs(45));
+    // Let exactly 45 seconds of overtime pass
+    clock.advance(Duration::from_secs(45));
 
     // Verify overtime exists
     assert_eq!(service.get_state().overtime_secs, Some(45));
@@ -764,15 +842,14 @@ fn test_overtime_cleared_on_clear() {
 
 #[test]
 fn test_overtime_cleared_on_phase_change() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session with overtime
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to exactly 20 seconds ago
-    let now = Instant::now();
-    service.completed_at = Some(now - Duration::from_secs(20));
+    // Let exactly 20 seconds of overtime pass
+    clock.advance(Duration::from_secs(20));
 
     // Verify overtime exists
     assert_eq!(service.get_state().overtime_secs, Some(20));
@@ -788,16 +865,15 @@ fn test_overtime_cleared_on_phase_change() {
 
 #[test]
 fn test_overtime_displayed_after_break_completion() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.set_phase(Phase::Break);
     service.start().unwrap();
 
     // Complete break session and wait 5 seconds
-    complete_break_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to exactly 5 seconds ago
-    let now = Instant::now();
-    service.completed_at = Some(now - Duration::from_secs(5));
+    // Let exactly 5 seconds of overtime pass
+    clock.advance(Duration::from_secs(5));
 
     let state = service.get_state();
     assert_eq!(state.status, Status::Complete);
@@ -806,16 +882,15 @@ fn test_overtime_displayed_after_break_completion() {
 
 #[test]
 fn test_overtime_break_cleared_on_start() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.set_phase(Phase::Break);
     service.start().unwrap();
 
     // Complete break session with overtime
-    complete_break_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to exactly 15 seconds ago
-    let now = Instant::now();
-    service.completed_at = Some(now - Duration::from_secs(15));
+    // Let exactly 15 seconds of overtime pass
+    clock.advance(Duration::from_secs(15));
 
     // Verify overtime exists
     assert_eq!(service.get_state().overtime_secs, Some(15));
@@ -832,35 +907,35 @@ fn test_overtime_break_cleared_on_start() {
 
 #[test]
 fn test_overtime_cap_beyond_59_59() {
-    let mut service = TimerService::new();
+    let (mut service, clock) = new_service();
     service.start().unwrap();
 
     // Complete work session
-    complete_work_session(&mut service);
+    complete_session(&mut service, &clock);
 
-    // Set completed_at to 1 hour and 30 seconds ago (3630 seconds)
-    service.completed_at = Some(Instant::now() - Duration::from_secs(3630));
+    // Exactly 1 hour of overtime (3600 seconds)
+    clock.advance(Duration::from_secs(3600));
 
-    let state = service.get_state();
+    let state2 = service.get_state();
     assert_eq!(
-        state.overtime_secs,
+        state2.overtime_secs,
         Some(3599),
-        "Overtime should cap at 3599 (59:59), got {:?}",
-        state.overtime_secs
+        "Overtime at exactly 60:00 should cap at 3599"
     );
 
-    // Set completed_at to exactly 1 hour ago (3600 seconds)
-    service.completed_at = Some(Instant::now() - Duration::from_secs(3600));
+    // 1 hour and 30 seconds of overtime (3630 seconds)
+    clock.advance(Duration::from_secs(30));
 
-    let state2 = service.get_state();
+    let state = service.get_state();
     assert_eq!(
-        state2.overtime_secs,
+        state.overtime_secs,
         Some(3599),
-        "Overtime at exactly 60:00 should cap at 3599"
+        "Overtime should cap at 3599 (59:59), got {:?}",
+        state.overtime_secs
     );
 
-    // Set completed_at to 2 hours ago (7200 seconds)
-    service.completed_at = Some(Instant::now() - Duration::from_secs(7200));
+    // 2 hours of overtime (7200 seconds)
+    clock.advance(Duration::from_secs(3570));
 
     let state3 = service.get_state();
     assert_eq!(
@@ -869,3 +944,2908 @@ fn test_overtime_cap_beyond_59_59() {
         "Overtime beyond 1 hour should remain capped at 3599"
     );
 }
+
+// ========== Configurable Durations ==========
+
+fn custom_config() -> TimerConfig {
+    TimerConfig {
+        work_duration_secs: 3000, // 50 minutes
+        break_duration_secs: 600, // 10 minutes
+        ..TimerConfig::default()
+    }
+}
+
+#[test]
+fn test_default_config_matches_standard_durations() {
+    let service = TimerService::new();
+    let config = service.config();
+
+    assert_eq!(config.work_duration_secs, WORK_DURATION_SECS);
+    assert_eq!(config.break_duration_secs, BREAK_DURATION_SECS);
+}
+
+#[test]
+fn test_set_config_rejects_out_of_range_durations() {
+    let mut service = TimerService::new();
+
+    let too_short = TimerConfig {
+        work_duration_secs: 30,
+        ..TimerConfig::default()
+    };
+    assert!(service.set_config(too_short).is_err());
+
+    let too_long = TimerConfig {
+        break_duration_secs: 7200,
+        ..TimerConfig::default()
+    };
+    let error = service.set_config(too_long).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "breakDurationSecs must be between 60 and 3600"
+    );
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({
+            "code": "invalidConfig",
+            "field": "breakDurationSecs",
+            "min": 60,
+            "max": 3600
+        })
+    );
+
+    // Rejected config leaves the previous one in place
+    assert_eq!(service.config(), TimerConfig::default());
+}
+
+#[test]
+fn test_set_config_updates_ready_state_immediately() {
+    let mut service = TimerService::new();
+    let state = service.set_config(custom_config()).unwrap();
+
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, 3000);
+    assert_eq!(state.duration_secs, 3000);
+}
+
+#[test]
+fn test_set_config_keeps_running_session_duration() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.set_config(custom_config()).unwrap();
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.duration_secs, WORK_DURATION_SECS);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+
+    // Next session uses the new duration
+    service.clear().unwrap();
+    service.start().unwrap();
+    let state = service.get_state();
+    assert_eq!(state.duration_secs, 3000);
+    assert_eq!(state.remaining_secs, 3000);
+}
+
+#[test]
+fn test_set_config_keeps_paused_session_remaining() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 300);
+    service.pause().unwrap();
+
+    let state = service.set_config(custom_config()).unwrap();
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 300);
+
+    service.resume().unwrap();
+    assert_eq!(service.get_state().remaining_secs, WORK_DURATION_SECS - 300);
+}
+
+#[test]
+fn test_set_config_applies_to_break_after_completion() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    service.set_config(custom_config()).unwrap();
+
+    // Completed session stays as is
+    assert_eq!(service.get_state().status, Status::Complete);
+
+    service.set_phase(Phase::Break);
+    let state = service.get_state();
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.remaining_secs, 600);
+    assert_eq!(state.duration_secs, 600);
+}
+
+#[test]
+fn test_parked_session_longer_than_new_config_keeps_remaining() {
+    let (mut service, clock) = new_service();
+    service.set_config(custom_config()).unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.pause().unwrap();
+    service.set_phase(Phase::Break);
+
+    // Shorten work below the parked remaining time
+    service.set_config(TimerConfig::default()).unwrap();
+    service.set_phase(Phase::Work);
+
+    let state = service.get_state();
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, 2940);
+    assert!(state.remaining_secs <= state.duration_secs);
+}
+
+// ========== Long Break Cycle ==========
+
+#[test]
+fn test_next_phase_is_long_break_every_fourth_work_session() {
+    let (mut service, clock) = new_service();
+
+    for cycle in 1..=LONG_BREAK_INTERVAL {
+        service.set_phase(Phase::Work);
+        service.start().unwrap();
+        complete_session(&mut service, &clock);
+
+        let state = service.get_state();
+        assert_eq!(state.completed_work_sessions, cycle);
+        let expected = if cycle == LONG_BREAK_INTERVAL {
+            Phase::LongBreak
+        } else {
+            Phase::Break
+        };
+        assert_eq!(state.next_phase, expected);
+
+        service.set_phase(expected);
+    }
+
+    // The cycle starts over after the long break
+    assert_eq!(service.get_state().next_phase, Phase::Work);
+    service.set_phase(Phase::Work);
+    assert_eq!(service.get_state().next_phase, Phase::Break);
+}
+
+#[test]
+fn test_next_phase_predicts_long_break_before_fourth_completion() {
+    let mut service = TimerService::new();
+    service.completed_work_sessions = LONG_BREAK_INTERVAL - 1;
+
+    // The running fourth session will be followed by a long break
+    service.start().unwrap();
+    assert_eq!(service.get_state().next_phase, Phase::LongBreak);
+}
+
+#[test]
+fn test_break_completion_does_not_count_as_work() {
+    let (mut service, clock) = new_service();
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    assert_eq!(service.get_state().completed_work_sessions, 0);
+}
+
+#[test]
+fn test_set_phase_to_long_break() {
+    let mut service = TimerService::new();
+    service.set_phase(Phase::LongBreak);
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::LongBreak);
+    assert_eq!(state.status, Status::LongBreakReady);
+    assert_eq!(state.remaining_secs, LONG_BREAK_DURATION_SECS);
+    assert_eq!(state.duration_secs, LONG_BREAK_DURATION_SECS);
+    assert_eq!(state.state_label, "Ready for long break");
+}
+
+#[test]
+fn test_long_break_run_pause_and_complete() {
+    let (mut service, clock) = new_service();
+    service.set_phase(Phase::LongBreak);
+    service.start().unwrap();
+    assert_eq!(service.get_state().state_label, "Long break time");
+
+    fast_forward(&mut service, &clock, 300);
+    service.pause().unwrap();
+    let state = service.get_state();
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, LONG_BREAK_DURATION_SECS - 300);
+    assert_eq!(state.state_label, "Paused (long break)");
+
+    // Parked long break survives a round trip through work
+    service.set_phase(Phase::Work);
+    service.set_phase(Phase::LongBreak);
+    assert_eq!(service.remaining_secs(), LONG_BREAK_DURATION_SECS - 300);
+    assert_eq!(service.status, Status::Paused);
+
+    service.resume().unwrap();
+    fast_forward(&mut service, &clock, LONG_BREAK_DURATION_SECS as u64);
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::LongBreak);
+    assert_eq!(state.status, Status::Complete);
+    assert_eq!(state.state_label, "Long break completed");
+}
+
+#[test]
+fn test_config_rejects_invalid_long_break_interval() {
+    let mut service = TimerService::new();
+    let config = TimerConfig {
+        long_break_interval: 0,
+        ..TimerConfig::default()
+    };
+
+    assert_eq!(
+        service.set_config(config).unwrap_err(),
+        TimerError::InvalidConfig {
+            field: "longBreakInterval",
+            min: 1,
+            max: 12
+        }
+    );
+}
+
+#[test]
+fn test_config_long_break_interval_changes_cycle() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(TimerConfig {
+            long_break_interval: 2,
+            ..TimerConfig::default()
+        })
+        .unwrap();
+
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    assert_eq!(service.get_state().next_phase, Phase::Break);
+
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    assert_eq!(service.get_state().next_phase, Phase::LongBreak);
+}
+
+// ========== Auto-advance on Completion ==========
+
+fn auto_config(on_work: CompletionAction, on_break: CompletionAction) -> TimerConfig {
+    TimerConfig {
+        on_work_complete: on_work,
+        on_break_complete: on_break,
+        ..TimerConfig::default()
+    }
+}
+
+#[test]
+fn test_work_completion_auto_starts_break() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Stay,
+        ))
+        .unwrap();
+    service.start().unwrap();
+
+    // Work reached zero 10 seconds ago
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 + 10);
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS - 10);
+    assert_eq!(state.duration_secs, BREAK_DURATION_SECS);
+    assert_eq!(state.state_label, "Break time");
+    assert!(state.completion_flag);
+    assert_eq!(state.overtime_secs, None);
+    assert_eq!(state.completed_work_sessions, 1);
+}
+
+#[test]
+fn test_work_completion_advances_to_break_ready() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::Advance,
+            CompletionAction::Stay,
+        ))
+        .unwrap();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS);
+    assert_eq!(state.overtime_secs, None);
+    assert!(state.completion_flag);
+    assert!(service.completed_at.is_none());
+
+    // Starting the break clears the flag again
+    service.start().unwrap();
+    assert!(!service.get_state().completion_flag);
+}
+
+#[test]
+fn test_break_completion_waits_in_work_ready() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Advance,
+        ))
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(
+        &mut service,
+        &clock,
+        (WORK_DURATION_SECS + BREAK_DURATION_SECS) as u64 + 30,
+    );
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Work);
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+    assert_eq!(state.overtime_secs, None);
+}
+
+#[test]
+fn test_auto_start_catches_up_over_several_sessions() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::AutoStart,
+        ))
+        .unwrap();
+    service.start().unwrap();
+
+    // Work, break, then 60 seconds into the next work session
+    fast_forward(
+        &mut service,
+        &clock,
+        (WORK_DURATION_SECS + BREAK_DURATION_SECS) as u64 + 60,
+    );
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Work);
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+    assert_eq!(state.completed_work_sessions, 1);
+}
+
+#[test]
+fn test_auto_start_picks_long_break_at_end_of_cycle() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Stay,
+        ))
+        .unwrap();
+    service.completed_work_sessions = LONG_BREAK_INTERVAL - 1;
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::LongBreak);
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.duration_secs, LONG_BREAK_DURATION_SECS);
+}
+
+#[test]
+fn test_auto_start_resumes_parked_break() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Stay,
+        ))
+        .unwrap();
+
+    // Park a break with 200 seconds left
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, BREAK_DURATION_SECS as u64 - 200);
+    service.pause().unwrap();
+    service.set_phase(Phase::Work);
+
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 + 20);
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, 180);
+}
+
+#[test]
+fn test_stay_keeps_overtime_behavior() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(CompletionAction::Stay, CompletionAction::Stay))
+        .unwrap();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    clock.advance(Duration::from_secs(10));
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Work);
+    assert_eq!(state.status, Status::Complete);
+    assert_eq!(state.overtime_secs, Some(10));
+}
+
+// ========== Snapshot / Restore ==========
+
+// Helper: snapshot the service, let `closed_secs` pass, and restore on the same clock
+fn restart_after(service: &TimerService, clock: &ManualClock, closed_secs: u64) -> TimerService {
+    let snapshot = service.snapshot();
+    clock.advance(Duration::from_secs(closed_secs));
+    TimerService::restore(snapshot, clock.clone())
+}
+
+#[test]
+fn test_restore_ready_state() {
+    let (mut service, clock) = new_service();
+    service.set_phase(Phase::Break);
+
+    let mut restored = restart_after(&service, &clock, 600);
+    let state = restored.get_state();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS);
+}
+
+#[test]
+fn test_restore_running_counts_time_while_closed() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 100);
+
+    let mut restored = restart_after(&service, &clock, 50);
+    let state = restored.get_state();
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 150);
+
+    // Keeps counting down after the restart
+    fast_forward(&mut restored, &clock, 10);
+    assert_eq!(
+        restored.get_state().remaining_secs,
+        WORK_DURATION_SECS - 160
+    );
+}
+
+#[test]
+fn test_restore_running_completes_while_closed() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 100);
+
+    // Closed long enough to finish the session and add 30 seconds of overtime
+    let mut restored = restart_after(&service, &clock, WORK_DURATION_SECS as u64 - 100 + 30);
+    let state = restored.get_state();
+    assert_eq!(state.status, Status::Complete);
+    assert_eq!(state.remaining_secs, 0);
+    assert!(state.completion_flag);
+    assert_eq!(state.overtime_secs, Some(30));
+    assert_eq!(state.completed_work_sessions, 1);
+}
+
+#[test]
+fn test_restore_paused_keeps_both_paused_phases() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 300);
+    service.pause().unwrap();
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.pause().unwrap();
+
+    let mut restored = restart_after(&service, &clock, 3600);
+    let state = restored.get_state();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS - 60);
+    assert_eq!(state.state_label, "Paused (break)");
+
+    restored.set_phase(Phase::Work);
+    assert_eq!(
+        restored.get_state().remaining_secs,
+        WORK_DURATION_SECS - 300
+    );
+}
+
+#[test]
+fn test_restore_complete_keeps_counting_overtime() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    clock.advance(Duration::from_secs(20));
+
+    let mut restored = restart_after(&service, &clock, 40);
+    let state = restored.get_state();
+    assert_eq!(state.status, Status::Complete);
+    assert_eq!(state.overtime_secs, Some(60));
+}
+
+#[test]
+fn test_restore_overtime_paused() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    clock.advance(Duration::from_secs(20));
+    service.pause().unwrap();
+
+    let mut restored = restart_after(&service, &clock, 300);
+    let state = restored.get_state();
+    assert_eq!(state.status, Status::OvertimePaused);
+    assert_eq!(state.overtime_paused_secs, Some(20));
+
+    restored.resume().unwrap();
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(restored.get_state().overtime_secs, Some(25));
+}
+
+#[test]
+fn test_restore_keeps_config_and_cycle_count() {
+    let (mut service, clock) = new_service();
+    service.set_config(custom_config()).unwrap();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    let restored = restart_after(&service, &clock, 10);
+    assert_eq!(restored.config(), custom_config());
+    assert_eq!(restored.completed_work_sessions, 1);
+}
+
+#[test]
+fn test_restore_replaces_invalid_config_with_defaults() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    let mut snapshot = service.snapshot();
+    snapshot.config.long_break_interval = 0;
+    snapshot.config.flowtime_break_divisor = 0;
+
+    let mut restored = TimerService::restore(snapshot, clock.clone());
+
+    assert_eq!(restored.config(), TimerConfig::default());
+    assert_eq!(restored.get_state().next_phase, Phase::Break);
+}
+
+#[test]
+fn test_restore_drops_invalid_program() {
+    let (mut service, clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+    let mut snapshot = service.snapshot();
+    snapshot.program.as_mut().unwrap().steps.clear();
+
+    let mut restored = TimerService::restore(snapshot, clock.clone());
+
+    assert_eq!(restored.program(), None);
+    assert_eq!(restored.get_state().step_index, None);
+}
+
+#[test]
+fn test_restore_moves_step_out_of_range_into_program() {
+    let (mut service, clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+    let mut snapshot = service.snapshot();
+    snapshot.step = 7;
+
+    let mut restored = TimerService::restore(snapshot, clock.clone());
+
+    assert_eq!(restored.get_state().step_index, Some(0));
+}
+
+#[test]
+fn test_snapshot_survives_json_round_trip() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 42);
+
+    let snapshot = service.snapshot();
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let parsed: TimerSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, snapshot);
+}
+
+// ========== Wall-clock Timestamps ==========
+
+// Helper: service whose wall clock starts at 2026-01-05 14:00:00 UTC
+fn new_service_at_2pm() -> (TimerService, ManualClock) {
+    let wall = "2026-01-05T14:00:00Z".parse::<DateTime<Utc>>().unwrap();
+    let clock = ManualClock::with_wall_time(wall);
+    (TimerService::with_clock(clock.clone()), clock)
+}
+
+#[test]
+fn test_ready_state_has_no_timestamps() {
+    let (mut service, _clock) = new_service_at_2pm();
+    let state = service.get_state();
+
+    assert_eq!(state.started_at, None);
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.ends_at, None);
+}
+
+#[test]
+fn test_running_state_reports_start_and_projected_end() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.get_state();
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:00:00Z"));
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.ends_at.as_deref(), Some("2026-01-05T14:25:00Z"));
+}
+
+#[test]
+fn test_pause_records_paused_at_and_resume_moves_end() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 300);
+    service.pause().unwrap();
+
+    let state = service.get_state();
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:00:00Z"));
+    assert_eq!(state.paused_at.as_deref(), Some("2026-01-05T14:05:00Z"));
+    assert_eq!(state.ends_at, None);
+
+    // Ten minutes later the session resumes and finishes ten minutes later
+    clock.advance(Duration::from_secs(600));
+    service.resume().unwrap();
+    let state = service.get_state();
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:00:00Z"));
+    assert_eq!(state.paused_at, None);
+    assert_eq!(state.ends_at.as_deref(), Some("2026-01-05T14:35:00Z"));
+}
+
+#[test]
+fn test_overtime_pause_records_paused_at() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    clock.advance(Duration::from_secs(60));
+    service.pause().unwrap();
+
+    let state = service.get_state();
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:00:00Z"));
+    assert_eq!(state.paused_at.as_deref(), Some("2026-01-05T14:26:00Z"));
+    assert_eq!(state.ends_at, None);
+}
+
+#[test]
+fn test_clear_and_phase_switch_drop_timestamps() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.pause().unwrap();
+    service.clear().unwrap();
+    let state = service.get_state();
+    assert_eq!(state.started_at, None);
+    assert_eq!(state.paused_at, None);
+
+    service.start().unwrap();
+    service.set_phase(Phase::Break);
+    let state = service.get_state();
+    assert_eq!(state.started_at, None);
+    assert_eq!(state.paused_at, None);
+}
+
+#[test]
+fn test_resuming_parked_session_starts_new_timestamp() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    clock.advance(Duration::from_secs(60));
+    service.resume().unwrap();
+    let state = service.get_state();
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:02:00Z"));
+    assert_eq!(state.ends_at.as_deref(), Some("2026-01-05T14:26:00Z"));
+}
+
+#[test]
+fn test_auto_started_session_starts_at_completion_time() {
+    let (mut service, clock) = new_service_at_2pm();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Stay,
+        ))
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 + 30);
+
+    let state = service.get_state();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:25:00Z"));
+    assert_eq!(state.ends_at.as_deref(), Some("2026-01-05T14:30:00Z"));
+}
+
+#[test]
+fn test_restore_keeps_session_timestamps() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 120);
+    service.pause().unwrap();
+
+    let mut restored = restart_after(&service, &clock, 60);
+    let state = restored.get_state();
+    assert_eq!(state.started_at.as_deref(), Some("2026-01-05T14:00:00Z"));
+    assert_eq!(state.paused_at.as_deref(), Some("2026-01-05T14:02:00Z"));
+}
+
+// ============================================================================
+// Timer events
+// ============================================================================
+
+#[test]
+fn test_completion_queues_one_event() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 10);
+
+    assert_eq!(
+        service.take_events(),
+        vec![TimerEvent::Completed {
+            phase: Phase::Work,
+            completed_at: "2026-01-05T14:25:00Z".to_string(),
+        }]
+    );
+    // Reading the state again does not repeat the event
+    service.get_state();
+    assert!(service.take_events().is_empty());
+}
+
+#[test]
+fn test_no_events_without_completion() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.pause().unwrap();
+    service.set_phase(Phase::Break);
+
+    assert!(service.take_events().is_empty());
+}
+
+#[test]
+fn test_auto_start_catch_up_queues_event_per_session() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::AutoStart,
+        ))
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(
+        &mut service,
+        &clock,
+        (WORK_DURATION_SECS + BREAK_DURATION_SECS + 10) as u64,
+    );
+
+    let phases: Vec<Phase> = service
+        .take_events()
+        .into_iter()
+        .filter_map(|event| match event {
+            TimerEvent::Completed { phase, .. } => Some(phase),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(phases, vec![Phase::Work, Phase::Break]);
+}
+
+#[test]
+fn test_completed_event_serializes_with_kind() {
+    let event = TimerEvent::Completed {
+        phase: Phase::LongBreak,
+        completed_at: "2026-01-05T14:25:00Z".to_string(),
+    };
+
+    assert_eq!(event.name(), "timer-completed");
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!({
+            "kind": "completed",
+            "phase": "longBreak",
+            "completedAt": "2026-01-05T14:25:00Z"
+        })
+    );
+}
+
+// ============================================================================
+// Session history
+// ============================================================================
+
+// Helper: wall-clock timestamp on the day used by `new_service_at_2pm`
+fn at(time: &str) -> chrono::DateTime<chrono::Utc> {
+    format!("2026-01-05T{}Z", time).parse().unwrap()
+}
+
+#[test]
+fn test_completed_session_recorded_when_restarted() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 300);
+    service.pause().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.resume().unwrap();
+    complete_session(&mut service, &clock);
+
+    // Overtime is only known once the completed session is left
+    fast_forward(&mut service, &clock, 90);
+    assert!(service.take_finished_sessions().is_empty());
+    service.start().unwrap();
+
+    assert_eq!(
+        service.take_finished_sessions(),
+        vec![SessionRecord {
+            ended_at: at("14:26:00"),
+            pause_count: 1,
+            paused_secs: 60,
+            overtime_secs: 90,
+            ..SessionRecord::sample(
+                Phase::Work,
+                SessionOutcome::Completed,
+                at("14:00:00"),
+                WORK_DURATION_SECS
+            )
+        }]
+    );
+}
+
+#[test]
+fn test_cleared_session_records_focused_and_paused_time() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+    service.pause().unwrap();
+    fast_forward(&mut service, &clock, 120);
+    service.clear().unwrap();
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::Cleared);
+    assert_eq!(records[0].focused_secs, 600);
+    assert_eq!(records[0].pause_count, 1);
+    assert_eq!(records[0].paused_secs, 120);
+    assert_eq!(records[0].ended_at, at("14:12:00"));
+}
+
+#[test]
+fn test_switching_phase_records_switched_away() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 200);
+    service.set_phase(Phase::Break);
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::SwitchedAway);
+    assert_eq!(records[0].focused_secs, 200);
+
+    // The parked remainder continues as a new session of its own
+    service.set_phase(Phase::Work);
+    service.resume().unwrap();
+    complete_session(&mut service, &clock);
+    service.clear().unwrap();
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::Completed);
+    assert_eq!(records[0].focused_secs, WORK_DURATION_SECS - 200);
+}
+
+#[test]
+fn test_untouched_phase_records_nothing() {
+    let mut service = TimerService::new();
+    service.set_phase(Phase::Break);
+    service.clear().unwrap();
+
+    assert!(service.take_finished_sessions().is_empty());
+}
+
+#[test]
+fn test_parked_session_shortened_to_zero_is_recorded() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 1200);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    let state = service.adjust_remaining(-600).unwrap();
+
+    assert_eq!(state.completed_work_sessions, 1);
+    assert_eq!(state.completed_today, 1);
+    service.start().unwrap();
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].outcome, SessionOutcome::SwitchedAway);
+    assert_eq!(records[1].phase, Phase::Work);
+    assert_eq!(records[1].outcome, SessionOutcome::Completed);
+    assert_eq!(records[1].adjustments[0].delta_secs, -300);
+}
+
+#[test]
+fn test_parked_session_counts_time_paused_before_resume() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 200);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+    service.take_finished_sessions();
+
+    fast_forward(&mut service, &clock, 60);
+    service.resume().unwrap();
+    fast_forward(&mut service, &clock, 100);
+    service.clear().unwrap();
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::Cleared);
+    assert_eq!(records[0].started_at, at("14:03:20"));
+    assert_eq!(records[0].focused_secs, 100);
+    assert_eq!(records[0].paused_secs, 60);
+}
+
+#[test]
+fn test_parked_session_cleared_before_resume_is_recorded() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 200);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+    service.take_finished_sessions();
+
+    service.clear().unwrap();
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::Cleared);
+    assert_eq!(records[0].focused_secs, 0);
+}
+
+#[test]
+fn test_switching_past_parked_session_records_it_once() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 200);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].focused_secs, 200);
+}
+
+#[test]
+fn test_auto_advance_records_completion_without_overtime() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Stay,
+        ))
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, WORK_DURATION_SECS as u64 + 30);
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].phase, Phase::Work);
+    assert_eq!(records[0].outcome, SessionOutcome::Completed);
+    assert_eq!(records[0].overtime_secs, 0);
+
+    // The auto-started break is tracked from the moment work ended
+    service.clear().unwrap();
+    let records = service.take_finished_sessions();
+    assert_eq!(records[0].phase, Phase::Break);
+    assert_eq!(records[0].focused_secs, 30);
+}
+
+#[test]
+fn test_session_in_progress_survives_restart() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 100);
+    service.pause().unwrap();
+
+    let mut restored = restart_after(&service, &clock, 50);
+    restored.clear().unwrap();
+
+    let records = restored.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].focused_secs, 100);
+    assert_eq!(records[0].pause_count, 1);
+    assert_eq!(records[0].paused_secs, 50);
+}
+
+// ============================================================================
+// Task tags
+// ============================================================================
+
+fn tag(task: &str, project: &str) -> TaskTag {
+    TaskTag::new(Some(task.to_string()), Some(project.to_string()))
+}
+
+#[test]
+fn test_start_with_tag_shows_task_in_state() {
+    let mut service = TimerService::new();
+
+    let state = service
+        .start_with_tag(Some(tag("Write report", "Acme")))
+        .unwrap();
+
+    assert_eq!(state.task.as_deref(), Some("Write report"));
+    assert_eq!(state.project.as_deref(), Some("Acme"));
+}
+
+#[test]
+fn test_tag_is_trimmed_and_blank_parts_dropped() {
+    let tag = TaskTag::new(Some("  Write report ".to_string()), Some("   ".to_string()));
+
+    assert_eq!(tag.task.as_deref(), Some("Write report"));
+    assert_eq!(tag.project, None);
+    assert!(TaskTag::new(None, Some(String::new())).is_empty());
+}
+
+#[test]
+fn test_tag_survives_pause_resume_and_phase_switch() {
+    let (mut service, clock) = new_service();
+    service
+        .start_with_tag(Some(tag("Write report", "Acme")))
+        .unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.pause().unwrap();
+    service.resume().unwrap();
+    assert_eq!(service.get_state().task.as_deref(), Some("Write report"));
+
+    // Breaks have their own tag
+    service.set_phase(Phase::Break);
+    assert_eq!(service.get_state().task, None);
+
+    service.set_phase(Phase::Work);
+    let state = service.resume().unwrap();
+    assert_eq!(state.task.as_deref(), Some("Write report"));
+    assert_eq!(state.project.as_deref(), Some("Acme"));
+}
+
+#[test]
+fn test_restart_without_tag_keeps_previous_tag() {
+    let (mut service, clock) = new_service();
+    service
+        .start_with_tag(Some(tag("Write report", "Acme")))
+        .unwrap();
+    complete_session(&mut service, &clock);
+
+    let state = service.start().unwrap();
+    assert_eq!(state.task.as_deref(), Some("Write report"));
+
+    // Clear forgets the tag along with everything else
+    let state = service.clear().unwrap();
+    assert_eq!(state.task, None);
+}
+
+#[test]
+fn test_session_records_carry_tag() {
+    let (mut service, clock) = new_service();
+    service
+        .start_with_tag(Some(tag("Write report", "Acme")))
+        .unwrap();
+    fast_forward(&mut service, &clock, 100);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+    service.resume().unwrap();
+    complete_session(&mut service, &clock);
+    service.clear().unwrap();
+
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 2);
+    assert!(
+        records
+            .iter()
+            .all(|record| record.tag == tag("Write report", "Acme"))
+    );
+    assert_eq!(
+        serde_json::to_value(&records[0]).unwrap()["project"],
+        serde_json::json!("Acme")
+    );
+}
+
+#[test]
+fn test_tag_survives_restart() {
+    let (mut service, clock) = new_service();
+    service
+        .start_with_tag(Some(tag("Write report", "Acme")))
+        .unwrap();
+
+    let mut restored = restart_after(&service, &clock, 30);
+
+    assert_eq!(restored.get_state().project.as_deref(), Some("Acme"));
+}
+
+// ============================================================================
+// Interruptions
+// ============================================================================
+
+#[test]
+fn test_interruptions_counted_in_state() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    service
+        .record_interruption(InterruptionKind::Internal, None)
+        .unwrap();
+    service.pause().unwrap();
+    let state = service
+        .record_interruption(InterruptionKind::External, Some("Phone".to_string()))
+        .unwrap();
+
+    assert_eq!(state.internal_interruptions, 1);
+    assert_eq!(state.external_interruptions, 1);
+    // Recording an interruption does not touch the countdown
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+}
+
+#[test]
+fn test_interruptions_rejected_outside_work_session() {
+    let (mut service, clock) = new_service();
+    assert_eq!(
+        service
+            .record_interruption(InterruptionKind::Internal, None)
+            .unwrap_err(),
+        TimerError::NotInWorkSession {
+            phase: Phase::Work,
+            status: Status::WorkReady
+        }
+    );
+
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    assert!(
+        service
+            .record_interruption(InterruptionKind::Internal, None)
+            .is_err()
+    );
+
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    let error = service
+        .record_interruption(InterruptionKind::External, None)
+        .unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({ "code": "notInWorkSession", "phase": "break", "status": "running" })
+    );
+}
+
+#[test]
+fn test_interruptions_stored_on_session_record() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 120);
+    service
+        .record_interruption(
+            InterruptionKind::External,
+            Some("  Phone call ".to_string()),
+        )
+        .unwrap();
+    service
+        .record_interruption(InterruptionKind::Internal, Some(" ".to_string()))
+        .unwrap();
+    service.clear().unwrap();
+
+    let records = service.take_finished_sessions();
+    assert_eq!(
+        records[0].interruptions,
+        vec![
+            Interruption {
+                kind: InterruptionKind::External,
+                at: at("14:02:00"),
+                note: Some("Phone call".to_string()),
+            },
+            Interruption {
+                kind: InterruptionKind::Internal,
+                at: at("14:02:00"),
+                note: None,
+            },
+        ]
+    );
+
+    // The next session starts with a clean count
+    let state = service.start().unwrap();
+    assert_eq!(state.internal_interruptions, 0);
+    assert_eq!(state.external_interruptions, 0);
+}
+
+#[test]
+fn test_interruptions_recorded_on_parked_session() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 300);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    let state = service
+        .record_interruption(InterruptionKind::Internal, None)
+        .unwrap();
+
+    assert_eq!(state.internal_interruptions, 1);
+    service.clear().unwrap();
+    let records = service.take_finished_sessions();
+    assert_eq!(records.last().unwrap().interruptions.len(), 1);
+}
+
+#[test]
+fn test_interruptions_survive_restart() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    service
+        .record_interruption(InterruptionKind::Internal, None)
+        .unwrap();
+
+    let mut restored = restart_after(&service, &clock, 10);
+
+    assert_eq!(restored.get_state().internal_interruptions, 1);
+}
+
+// ============================================================================
+// Daily goal
+// ============================================================================
+
+fn goal_config(daily_goal: u32) -> TimerConfig {
+    TimerConfig {
+        daily_goal,
+        ..TimerConfig::default()
+    }
+}
+
+// Helper: run `count` work sessions back to back, staying in work
+fn complete_work_sessions(service: &mut TimerService, clock: &ManualClock, count: u32) {
+    for _ in 0..count {
+        service.start().unwrap();
+        complete_session(service, clock);
+    }
+}
+
+#[test]
+fn test_state_reports_daily_progress() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.set_config(goal_config(4)).unwrap();
+
+    complete_work_sessions(&mut service, &clock, 2);
+    // Breaks do not count towards the goal
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    let state = service.get_state();
+    assert_eq!(state.completed_today, 2);
+    assert_eq!(state.daily_goal, 4);
+}
+
+#[test]
+fn test_goal_reached_event_fires_once() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.set_config(goal_config(2)).unwrap();
+
+    complete_work_sessions(&mut service, &clock, 3);
+
+    let goal_events: Vec<TimerEvent> = service
+        .take_events()
+        .into_iter()
+        .filter(|event| matches!(event, TimerEvent::GoalReached { .. }))
+        .collect();
+    assert_eq!(
+        goal_events,
+        vec![TimerEvent::GoalReached {
+            completed_today: 2,
+            daily_goal: 2,
+            reached_at: "2026-01-05T14:50:00Z".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_goal_is_not_announced_again_after_extending() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.set_config(goal_config(1)).unwrap();
+    complete_work_sessions(&mut service, &clock, 1);
+
+    service.adjust_remaining(60).unwrap();
+    complete_session(&mut service, &clock);
+
+    let goal_events = service
+        .take_events()
+        .into_iter()
+        .filter(|event| matches!(event, TimerEvent::GoalReached { .. }))
+        .count();
+    assert_eq!(goal_events, 1);
+    assert_eq!(service.get_state().completed_today, 1);
+}
+
+#[test]
+fn test_daily_count_resets_at_rollover_hour() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.set_config(goal_config(2)).unwrap();
+    complete_work_sessions(&mut service, &clock, 2);
+    service.take_events();
+
+    // Up to 03:59 the next morning still belongs to the 5th
+    fast_forward(&mut service, &clock, 13 * 3600 + 9 * 60);
+    assert_eq!(service.get_state().completed_today, 2);
+
+    // The new day starts at 04:00, before anything is completed in it
+    fast_forward(&mut service, &clock, 60);
+    assert_eq!(service.get_state().completed_today, 0);
+
+    // And the goal can be reached again
+    service.clear().unwrap();
+    complete_work_sessions(&mut service, &clock, 2);
+    assert!(
+        service
+            .take_events()
+            .iter()
+            .any(|event| matches!(event, TimerEvent::GoalReached { .. }))
+    );
+}
+
+#[test]
+fn test_rollover_uses_local_time() {
+    // At UTC-9:30 the session completes at 03:55 local time, which still
+    // counts for the previous day
+    let offset = chrono::FixedOffset::west_opt(9 * 3600 + 30 * 60).unwrap();
+    let wall = "2026-01-05T13:00:00Z".parse::<DateTime<Utc>>().unwrap();
+    let clock = ManualClock::with_wall_time(wall).with_offset(offset);
+    let mut service = TimerService::with_clock(clock.clone());
+
+    complete_work_sessions(&mut service, &clock, 1);
+    fast_forward(&mut service, &clock, 35 * 60);
+
+    // By 04:30 local time the new day has started with nothing completed
+    assert_eq!(service.get_state().completed_today, 0);
+}
+
+#[test]
+fn test_config_rejects_invalid_goal_and_rollover() {
+    let mut service = TimerService::new();
+
+    assert!(service.set_config(goal_config(0)).is_err());
+    let error = service
+        .set_config(TimerConfig {
+            rollover_hour: 24,
+            ..TimerConfig::default()
+        })
+        .unwrap_err();
+    assert_eq!(error.to_string(), "rolloverHour must be between 0 and 23");
+}
+
+#[test]
+fn test_daily_count_survives_restart() {
+    let (mut service, clock) = new_service_at_2pm();
+    complete_work_sessions(&mut service, &clock, 1);
+
+    let mut restored = restart_after(&service, &clock, 60);
+
+    assert_eq!(restored.get_state().completed_today, 1);
+}
+
+// ============================================================================
+// Programs
+// ============================================================================
+
+fn step(phase: Phase, minutes: u32, name: Option<&str>) -> ProgramStep {
+    ProgramStep {
+        phase,
+        duration_secs: minutes * 60,
+        name: name.map(str::to_string),
+    }
+}
+
+// Helper: "Warm-up 15, Work 50, Break 10, Work 50, Long break 30"
+fn sample_program() -> Program {
+    Program {
+        name: "Deep work".to_string(),
+        steps: vec![
+            step(Phase::Work, 15, Some("Warm-up")),
+            step(Phase::Work, 50, None),
+            step(Phase::Break, 10, None),
+            step(Phase::Work, 50, None),
+            step(Phase::LongBreak, 30, None),
+        ],
+    }
+}
+
+#[test]
+fn test_set_program_moves_ready_state_to_first_step() {
+    let (mut service, _clock) = new_service();
+    service.set_phase(Phase::Break);
+
+    let state = service.set_program(Some(sample_program())).unwrap();
+
+    assert_eq!(state.phase, Phase::Work);
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.duration_secs, 900);
+    assert_eq!(state.step_index, Some(0));
+    assert_eq!(state.step_name.as_deref(), Some("Warm-up"));
+    assert_eq!(state.next_step, Some(step(Phase::Work, 50, None)));
+    assert_eq!(state.next_phase, Phase::Work);
+}
+
+#[test]
+fn test_program_walks_through_steps_and_wraps() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::Advance,
+            CompletionAction::Advance,
+        ))
+        .unwrap();
+    service.set_program(Some(sample_program())).unwrap();
+
+    let mut walked = Vec::new();
+    for _ in 0..5 {
+        service.start().unwrap();
+        complete_session(&mut service, &clock);
+        let state = service.get_state();
+        walked.push((state.step_index, state.phase, state.duration_secs));
+    }
+
+    assert_eq!(
+        walked,
+        vec![
+            (Some(1), Phase::Work, 3000),
+            (Some(2), Phase::Break, 600),
+            (Some(3), Phase::Work, 3000),
+            (Some(4), Phase::LongBreak, 1800),
+            (Some(0), Phase::Work, 900),
+        ]
+    );
+}
+
+#[test]
+fn test_set_phase_moves_to_next_step_of_that_phase() {
+    let (mut service, _clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+
+    service.set_phase(Phase::Break);
+    assert_eq!(service.get_state().step_index, Some(2));
+    service.set_phase(Phase::LongBreak);
+    assert_eq!(service.get_state().step_index, Some(4));
+
+    // Searching wraps around to the start of the program
+    service.set_phase(Phase::Work);
+    let state = service.get_state();
+    assert_eq!(state.step_index, Some(0));
+    assert_eq!(state.duration_secs, 900);
+}
+
+#[test]
+fn test_set_step_jumps_to_any_step() {
+    let (mut service, clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    // A different step of the same phase starts from its full duration
+    let state = service.set_step(3).unwrap();
+
+    assert_eq!(state.step_index, Some(3));
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, 3000);
+    assert_eq!(
+        service.take_finished_sessions()[0].outcome,
+        SessionOutcome::Skipped
+    );
+
+    // Nothing of the step left is parked to come back to
+    let state = service.set_step(1).unwrap();
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, 3000);
+}
+
+#[test]
+fn test_set_step_parks_session_of_another_phase() {
+    let (mut service, clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 500);
+
+    service.set_step(2).unwrap();
+    assert_eq!(
+        service.take_finished_sessions()[0].outcome,
+        SessionOutcome::SwitchedAway
+    );
+
+    // Jumping back to the step continues its parked time
+    let state = service.set_step(0).unwrap();
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, 400);
+
+    // Another step of that phase does not
+    service.set_step(2).unwrap();
+    let state = service.set_step(3).unwrap();
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, 3000);
+}
+
+#[test]
+fn test_set_step_is_flagged_while_time_would_be_lost() {
+    let (mut service, _clock) = new_service();
+    assert!(
+        !service
+            .get_state()
+            .available_actions
+            .contains(&TimerAction::SetStep)
+    );
+
+    service.set_program(Some(sample_program())).unwrap();
+    let state = service.get_state();
+    assert!(state.available_actions.contains(&TimerAction::SetStep));
+    assert!(state.destructive_actions.is_empty());
+
+    let state = service.start().unwrap();
+    assert!(state.destructive_actions.contains(&TimerAction::SetStep));
+}
+
+#[test]
+fn test_set_step_rejects_unknown_step() {
+    let (mut service, _clock) = new_service();
+    assert_eq!(
+        service.set_step(0).unwrap_err(),
+        TimerError::InvalidStep {
+            step: 0,
+            step_count: 0
+        }
+    );
+
+    service.set_program(Some(sample_program())).unwrap();
+    let error = service.set_step(5).unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({ "code": "invalidStep", "step": 5, "stepCount": 5 })
+    );
+}
+
+#[test]
+fn test_set_program_rejects_invalid_steps() {
+    let mut service = TimerService::new();
+
+    let empty = Program {
+        name: "Empty".to_string(),
+        steps: Vec::new(),
+    };
+    assert!(service.set_program(Some(empty)).is_err());
+
+    let mut too_long_break = sample_program();
+    too_long_break.steps[2].duration_secs = 7200;
+    assert_eq!(
+        service.set_program(Some(too_long_break)).unwrap_err(),
+        TimerError::InvalidConfig {
+            field: "durationSecs",
+            min: 60,
+            max: 3600
+        }
+    );
+    assert_eq!(service.program(), None);
+}
+
+#[test]
+fn test_set_program_keeps_session_in_progress() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.set_program(Some(sample_program())).unwrap();
+
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+    assert_eq!(state.step_index, Some(0));
+}
+
+#[test]
+fn test_switching_back_continues_step_with_parked_time() {
+    let (mut service, clock) = new_service();
+    service
+        .set_program(Some(Program {
+            name: "Warm-up first".to_string(),
+            steps: vec![
+                step(Phase::Work, 10, Some("warm")),
+                step(Phase::Break, 1, None),
+                step(Phase::Work, 50, Some("deep")),
+            ],
+        }))
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 100);
+
+    service.set_phase(Phase::Break);
+    assert_eq!(service.get_state().step_index, Some(1));
+    service.set_phase(Phase::Work);
+
+    let state = service.get_state();
+    assert_eq!(state.step_index, Some(0));
+    assert_eq!(state.step_name.as_deref(), Some("warm"));
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, 500);
+    assert_eq!(state.duration_secs, 600);
+}
+
+#[test]
+fn test_clearing_program_restores_default_cycle() {
+    let (mut service, _clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+
+    let state = service.set_program(None).unwrap();
+
+    assert_eq!(state.duration_secs, WORK_DURATION_SECS);
+    assert_eq!(state.step_index, None);
+    assert_eq!(state.next_step, None);
+    assert_eq!(state.next_phase, Phase::Break);
+}
+
+#[test]
+fn test_program_survives_restart() {
+    let (mut service, clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+    service.set_step(2).unwrap();
+
+    let mut restored = restart_after(&service, &clock, 10);
+
+    assert_eq!(restored.program(), Some(&sample_program()));
+    assert_eq!(restored.get_state().step_index, Some(2));
+}
+
+// ============================================================================
+// Flowtime
+// ============================================================================
+
+fn flowtime_config() -> TimerConfig {
+    TimerConfig {
+        mode: TimerMode::Flowtime,
+        ..TimerConfig::default()
+    }
+}
+
+// Helper: service in Flowtime mode
+fn new_flowtime_service() -> (TimerService, ManualClock) {
+    let (mut service, clock) = new_service_at_2pm();
+    service.set_config(flowtime_config()).unwrap();
+    (service, clock)
+}
+
+#[test]
+fn test_flowtime_work_counts_up_without_end() {
+    let (mut service, clock) = new_flowtime_service();
+    let ready = service.get_state();
+    assert!(ready.counting_up);
+    assert_eq!(ready.elapsed_secs, Some(0));
+    assert_eq!(ready.duration_secs, 0);
+
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 5 * 3600);
+
+    let state = service.get_state();
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.elapsed_secs, Some(5 * 3600));
+    assert_eq!(state.remaining_secs, 0);
+    assert_eq!(state.ends_at, None);
+    assert!(service.take_events().is_empty());
+}
+
+#[test]
+fn test_flowtime_pause_stops_counting() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 300);
+    service.pause().unwrap();
+    fast_forward(&mut service, &clock, 100);
+    service.resume().unwrap();
+    fast_forward(&mut service, &clock, 200);
+
+    assert_eq!(service.get_state().elapsed_secs, Some(500));
+}
+
+#[test]
+fn test_stop_work_loads_proportional_break() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 3000);
+
+    let state = service.stop_work().unwrap();
+
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.duration_secs, 600);
+    assert_eq!(state.remaining_secs, 600);
+    assert!(!state.counting_up);
+    assert!(state.completion_flag);
+    assert_eq!(state.completed_work_sessions, 1);
+    assert_eq!(
+        service.take_events(),
+        vec![TimerEvent::Completed {
+            phase: Phase::Work,
+            completed_at: "2026-01-05T14:50:00Z".to_string(),
+        }]
+    );
+    let record = &service.take_finished_sessions()[0];
+    assert_eq!(record.outcome, SessionOutcome::Completed);
+    assert_eq!(record.planned_secs, 0);
+    assert_eq!(record.focused_secs, 3000);
+}
+
+#[test]
+fn test_stop_work_on_parked_session_is_recorded() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 1800);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    let state = service.stop_work().unwrap();
+
+    assert_eq!(state.completed_work_sessions, 1);
+    assert_eq!(state.duration_secs, 360);
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].outcome, SessionOutcome::SwitchedAway);
+    assert_eq!(records[0].focused_secs, 1800);
+    assert_eq!(records[1].phase, Phase::Work);
+    assert_eq!(records[1].outcome, SessionOutcome::Completed);
+    assert_eq!(records[1].ended_at, at("14:30:00"));
+}
+
+#[test]
+fn test_stop_work_keeps_break_within_limits() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 120);
+    assert_eq!(service.stop_work().unwrap().duration_secs, 60);
+
+    service.set_phase(Phase::Work);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 8 * 3600);
+    assert_eq!(service.stop_work().unwrap().duration_secs, 3600);
+}
+
+#[test]
+fn test_stop_work_auto_starts_break() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(TimerConfig {
+            on_work_complete: CompletionAction::AutoStart,
+            flowtime_break_divisor: 3,
+            ..flowtime_config()
+        })
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 1800);
+    service.pause().unwrap();
+
+    let state = service.stop_work().unwrap();
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, 600);
+
+    // The earned break is used once; the next break is the configured one
+    complete_session(&mut service, &clock);
+    let state = service.start().unwrap();
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS);
+}
+
+#[test]
+fn test_new_work_forfeits_unused_flowtime_break() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 3000);
+    service.stop_work().unwrap();
+
+    service.set_phase(Phase::Work);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    let state = service.skip().unwrap();
+
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.duration_secs, BREAK_DURATION_SECS);
+}
+
+#[test]
+fn test_stop_work_requires_count_up_session() {
+    let (mut service, _clock) = new_service();
+    service.start().unwrap();
+    assert_eq!(
+        service.stop_work().unwrap_err(),
+        TimerError::NotCountingUp {
+            phase: Phase::Work,
+            status: Status::Running
+        }
+    );
+
+    let (mut service, _clock) = new_flowtime_service();
+    let error = service.stop_work().unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({ "code": "notCountingUp", "phase": "work", "status": "workReady" })
+    );
+}
+
+#[test]
+fn test_mode_change_keeps_session_in_progress() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+
+    service.set_config(TimerConfig::default()).unwrap();
+    fast_forward(&mut service, &clock, 600);
+    assert_eq!(service.get_state().elapsed_secs, Some(1200));
+
+    // The next work session counts down again
+    service.stop_work().unwrap();
+    service.set_phase(Phase::Work);
+    let state = service.get_state();
+    assert!(!state.counting_up);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+}
+
+#[test]
+fn test_flowtime_session_survives_restart() {
+    let (mut service, clock) = new_flowtime_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+
+    let mut restored = restart_after(&service, &clock, 60);
+    clock.advance(Duration::from_secs(30));
+
+    assert_eq!(restored.get_state().elapsed_secs, Some(690));
+}
+
+// ============================================================================
+// Adjusting the remaining time
+// ============================================================================
+
+#[test]
+fn test_extend_running_session() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+
+    let state = service.adjust_remaining(300).unwrap();
+    assert_eq!(state.remaining_secs, 1200);
+    assert_eq!(state.duration_secs, 1800);
+    assert_eq!(state.ends_at.as_deref(), Some("2026-01-05T14:30:00Z"));
+
+    fast_forward(&mut service, &clock, 100);
+    assert_eq!(service.get_state().remaining_secs, 1100);
+}
+
+#[test]
+fn test_shorten_paused_session_keeps_parked_time() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+    service.pause().unwrap();
+
+    let state = service.adjust_remaining(-300).unwrap();
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, 600);
+    assert_eq!(state.duration_secs, 1200);
+
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+    assert_eq!(service.get_state().remaining_secs, 600);
+    service.resume().unwrap();
+    fast_forward(&mut service, &clock, 100);
+    assert_eq!(service.get_state().remaining_secs, 500);
+}
+
+#[test]
+fn test_extend_completed_session_leaves_overtime() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 30);
+
+    let state = service.adjust_remaining(300).unwrap();
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, 300);
+    assert_eq!(state.duration_secs, 1800);
+    assert_eq!(state.overtime_secs, None);
+    assert!(!state.completion_flag);
+    assert_eq!(state.completed_work_sessions, 0);
+    assert_eq!(state.completed_today, 0);
+
+    // It completes, and counts, once more
+    complete_session(&mut service, &clock);
+    assert_eq!(service.get_state().completed_work_sessions, 1);
+    service.start().unwrap();
+    let record = &service.take_finished_sessions()[0];
+    assert_eq!(record.outcome, SessionOutcome::Completed);
+    assert_eq!(record.focused_secs, 1800);
+    assert_eq!(
+        record.adjustments,
+        vec![Adjustment {
+            at: at("14:25:30"),
+            delta_secs: 300
+        }]
+    );
+}
+
+#[test]
+fn test_adjusting_parked_session_is_recorded() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+    service.take_finished_sessions();
+
+    service.adjust_remaining(120).unwrap();
+    service.resume().unwrap();
+    fast_forward(&mut service, &clock, 100);
+    service.clear().unwrap();
+
+    let record = &service.take_finished_sessions()[0];
+    assert_eq!(record.focused_secs, 100);
+    assert_eq!(
+        record.adjustments,
+        vec![Adjustment {
+            at: at("14:10:00"),
+            delta_secs: 120
+        }]
+    );
+}
+
+#[test]
+fn test_shortening_to_nothing_completes_session() {
+    let (mut service, clock) = new_service();
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.adjust_remaining(-3600).unwrap();
+    assert_eq!(state.status, Status::Complete);
+    assert!(state.completion_flag);
+    assert_eq!(service.take_events().len(), 1);
+
+    service.clear().unwrap();
+    let record = &service.take_finished_sessions()[0];
+    assert_eq!(record.focused_secs, 60);
+    assert_eq!(record.adjustments[0].delta_secs, -240);
+}
+
+#[test]
+fn test_adjust_limits_and_rejections() {
+    let (mut service, clock) = new_service();
+    assert_eq!(
+        service.adjust_remaining(60).unwrap_err(),
+        TimerError::NotAdjustable {
+            status: Status::WorkReady
+        }
+    );
+
+    // Extending is capped at the longest allowed work session
+    service.start().unwrap();
+    assert_eq!(
+        service.adjust_remaining(20000).unwrap().remaining_secs,
+        10800
+    );
+
+    // Shortening a completed session has nothing to take away
+    complete_session(&mut service, &clock);
+    let state = service.adjust_remaining(-60).unwrap();
+    assert_eq!(state.status, Status::Complete);
+
+    let (mut service, _clock) = new_flowtime_service();
+    service.start().unwrap();
+    let error = service.adjust_remaining(60).unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({ "code": "notAdjustable", "status": "running" })
+    );
+}
+
+// ============================================================================
+// Skip
+// ============================================================================
+
+#[test]
+fn test_skip_records_skipped_and_moves_to_next_ready() {
+    let (mut service, clock) = new_service_at_2pm();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+
+    let state = service.skip().unwrap();
+
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS);
+    assert_eq!(state.completed_work_sessions, 0);
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::Skipped);
+    assert_eq!(records[0].focused_secs, 600);
+    assert_eq!(records[0].ended_at, at("14:10:00"));
+}
+
+#[test]
+fn test_skip_drops_leftover_time() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+    service.pause().unwrap();
+
+    service.skip().unwrap();
+    service.set_phase(Phase::Work);
+
+    // Nothing parked to come back to
+    let state = service.get_state();
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+}
+
+#[test]
+fn test_skip_starts_next_phase_fresh() {
+    let (mut service, clock) = new_service();
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.set_phase(Phase::Work);
+    service.set_phase(Phase::Break);
+    service.set_phase(Phase::Work);
+
+    // Skipping the ready work session leads to a fresh break, not the parked one
+    let state = service.skip().unwrap();
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.remaining_secs, BREAK_DURATION_SECS);
+}
+
+#[test]
+fn test_skip_honours_auto_start() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(auto_config(
+            CompletionAction::AutoStart,
+            CompletionAction::Advance,
+        ))
+        .unwrap();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.skip().unwrap();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::Running);
+
+    // Breaks only advance, so skipping one waits in work's Ready state
+    let state = service.skip().unwrap();
+    assert_eq!(state.phase, Phase::Work);
+    assert_eq!(state.status, Status::WorkReady);
+}
+
+#[test]
+fn test_skipped_work_does_not_earn_the_long_break() {
+    let (mut service, clock) = new_service();
+    for _ in 0..3 {
+        service.start().unwrap();
+        complete_session(&mut service, &clock);
+    }
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.skip().unwrap();
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.completed_work_sessions, 3);
+
+    // The long break comes after the 4th completed work session, once
+    service.set_phase(Phase::Work);
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    let state = service.skip().unwrap();
+    assert_eq!(state.phase, Phase::LongBreak);
+    service.skip().unwrap();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    assert_eq!(service.get_state().next_phase, Phase::Break);
+}
+
+#[test]
+fn test_skipping_ready_work_leads_to_short_break() {
+    let (mut service, clock) = new_service();
+    for _ in 0..3 {
+        service.start().unwrap();
+        complete_session(&mut service, &clock);
+    }
+    service.clear().unwrap();
+
+    let state = service.skip().unwrap();
+
+    assert_eq!(state.phase, Phase::Break);
+}
+
+#[test]
+fn test_skip_after_completion_keeps_completed_outcome() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 45);
+
+    service.skip().unwrap();
+
+    let record = &service.take_finished_sessions()[0];
+    assert_eq!(record.outcome, SessionOutcome::Completed);
+    assert_eq!(record.overtime_secs, 45);
+}
+
+// ============================================================================
+// Undo
+// ============================================================================
+
+#[test]
+fn test_undo_clear_restores_running_session() {
+    let (mut service, clock) = new_service();
+    service.undoable(TimerService::start).unwrap();
+    fast_forward(&mut service, &clock, 600);
+    service.undoable(TimerService::clear).unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.undo().unwrap();
+
+    // The minute after the clear still counted
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 660);
+    assert!(state.can_undo);
+    // The cleared session never happened
+    assert!(service.take_finished_sessions().is_empty());
+}
+
+#[test]
+fn test_undo_pause_counts_paused_time() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 100);
+    service.undoable(TimerService::pause).unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    let state = service.undo().unwrap();
+
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 160);
+    assert!(!state.can_undo);
+}
+
+#[test]
+fn test_undo_completes_session_that_ran_out_meanwhile() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 1400);
+    service
+        .undoable(|service| {
+            service.set_phase(Phase::Break);
+            Ok(())
+        })
+        .unwrap();
+    fast_forward(&mut service, &clock, 200);
+    service.take_events();
+
+    let state = service.undo().unwrap();
+
+    assert_eq!(state.phase, Phase::Work);
+    assert_eq!(state.status, Status::Complete);
+    assert_eq!(state.overtime_secs, Some(100));
+    assert_eq!(service.take_events().len(), 1);
+}
+
+#[test]
+fn test_undo_withdraws_sessions_already_taken() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 600);
+    service.undoable(TimerService::skip).unwrap();
+    let skipped = service.take_finished_sessions();
+
+    service.undo().unwrap();
+
+    assert_eq!(service.take_undone_sessions(), skipped);
+    assert!(service.take_undone_sessions().is_empty());
+}
+
+#[test]
+fn test_undo_stack_is_bounded() {
+    let (mut service, _clock) = new_service();
+    service.start().unwrap();
+    for _ in 0..15 {
+        service.undoable(TimerService::pause).unwrap();
+        service.undoable(TimerService::resume).unwrap();
+    }
+
+    for _ in 0..undo::UNDO_LIMIT {
+        service.undo().unwrap();
+    }
+    assert_eq!(service.undo().unwrap_err(), TimerError::NothingToUndo);
+}
+
+#[test]
+fn test_failed_action_leaves_nothing_to_undo() {
+    let (mut service, _clock) = new_service();
+    service.start().unwrap();
+
+    assert!(service.undoable(TimerService::start).is_err());
+    assert!(!service.can_undo());
+}
+
+#[test]
+fn test_undo_keeps_current_config() {
+    let (mut service, _clock) = new_service();
+    service.undoable(TimerService::start).unwrap();
+    service.set_config(custom_config()).unwrap();
+
+    let state = service.undo().unwrap();
+
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, 3000);
+    assert_eq!(service.config(), custom_config());
+}
+
+#[test]
+fn test_undo_after_program_change_uses_step_of_current_program() {
+    let (mut service, _clock) = new_service();
+    service.set_program(Some(sample_program())).unwrap();
+    service.set_step(2).unwrap();
+    service.undoable(TimerService::start).unwrap();
+    service
+        .set_program(Some(Program {
+            name: "Break first".to_string(),
+            steps: vec![
+                step(Phase::Break, 10, None),
+                step(Phase::Work, 25, None),
+                step(Phase::Work, 50, None),
+            ],
+        }))
+        .unwrap();
+
+    let state = service.undo().unwrap();
+
+    assert_eq!(state.phase, Phase::Break);
+    assert_eq!(state.status, Status::BreakReady);
+    assert_eq!(state.step_index, Some(0));
+    assert_eq!(state.duration_secs, 600);
+}
+
+// ========== Available Actions ==========
+
+// Helper: run the command behind an action
+fn run_action(service: &mut TimerService, action: TimerAction) -> Result<TimerState, TimerError> {
+    match action {
+        TimerAction::Start => service.start(),
+        TimerAction::Pause => service.pause(),
+        TimerAction::Resume => service.resume(),
+        TimerAction::StopWork => service.stop_work(),
+        TimerAction::Extend => service.adjust_remaining(60),
+        TimerAction::Shorten => service.adjust_remaining(-60),
+        TimerAction::Skip => service.skip(),
+        TimerAction::Clear => service.clear(),
+        TimerAction::Undo => service.undo(),
+        TimerAction::RecordInterruption => {
+            service.record_interruption(InterruptionKind::Internal, None)
+        }
+        TimerAction::SetStep => service.set_step(0),
+    }
+}
+
+#[test]
+fn test_fresh_state_offers_start_and_skip() {
+    let (mut service, _clock) = new_service();
+
+    let state = service.get_state();
+
+    assert_eq!(
+        state.available_actions,
+        vec![TimerAction::Start, TimerAction::Skip]
+    );
+    assert!(state.destructive_actions.is_empty());
+}
+
+#[test]
+fn test_running_work_offers_pause_and_flags_skip_and_clear() {
+    let (mut service, _clock) = new_service();
+
+    let state = service.start().unwrap();
+
+    assert_eq!(
+        state.available_actions,
+        vec![
+            TimerAction::Pause,
+            TimerAction::Extend,
+            TimerAction::Shorten,
+            TimerAction::Skip,
+            TimerAction::Clear,
+            TimerAction::RecordInterruption,
+        ]
+    );
+    assert_eq!(
+        state.destructive_actions,
+        vec![TimerAction::Skip, TimerAction::Clear]
+    );
+}
+
+#[test]
+fn test_completed_session_cannot_be_shortened() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    let state = service.get_state();
+
+    assert_eq!(
+        state.available_actions,
+        vec![
+            TimerAction::Start,
+            TimerAction::Pause,
+            TimerAction::Extend,
+            TimerAction::Skip,
+            TimerAction::Clear,
+        ]
+    );
+    assert!(state.destructive_actions.is_empty());
+}
+
+#[test]
+fn test_clearing_parked_session_needs_confirmation() {
+    let (mut service, _clock) = new_service();
+    service.start().unwrap();
+    service.set_phase(Phase::Break);
+
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::BreakReady);
+    assert!(state.available_actions.contains(&TimerAction::Clear));
+    // Skipping to work would drop the parked work session too
+    assert_eq!(
+        state.destructive_actions,
+        vec![TimerAction::Skip, TimerAction::Clear]
+    );
+}
+
+#[test]
+fn test_skip_keeps_time_parked_in_other_phases() {
+    let (mut service, _clock) = new_service();
+    service.set_phase(Phase::LongBreak);
+    service.start().unwrap();
+    service.set_phase(Phase::Work);
+
+    let state = service.get_state();
+
+    assert_eq!(state.next_phase, Phase::Break);
+    assert_eq!(state.destructive_actions, vec![TimerAction::Clear]);
+}
+
+#[test]
+fn test_flowtime_work_offers_stop_instead_of_adjusting() {
+    let (mut service, _clock) = new_flowtime_service();
+
+    let state = service.start().unwrap();
+
+    assert!(state.available_actions.contains(&TimerAction::StopWork));
+    assert!(!state.available_actions.contains(&TimerAction::Extend));
+    assert!(!state.available_actions.contains(&TimerAction::Shorten));
+}
+
+#[test]
+fn test_listed_actions_match_command_results() {
+    let setups: [fn(&mut TimerService, &ManualClock); 6] = [
+        |_, _| {},
+        |service, _| {
+            service.start().unwrap();
+        },
+        |service, _| {
+            service.start().unwrap();
+            service.pause().unwrap();
+        },
+        |service, clock| {
+            service.start().unwrap();
+            complete_session(service, clock);
+        },
+        |service, clock| {
+            service.start().unwrap();
+            complete_session(service, clock);
+            service.pause().unwrap();
+        },
+        |service, _| {
+            service.undoable(TimerService::start).unwrap();
+            service.set_phase(Phase::LongBreak);
+        },
+    ];
+
+    for (index, setup) in setups.iter().enumerate() {
+        for action in TimerAction::ALL {
+            let (mut service, clock) = new_service();
+            setup(&mut service, &clock);
+            let listed = service.get_state().available_actions.contains(&action);
+            let accepted = service.check_action(action);
+
+            let result = run_action(&mut service, action);
+
+            assert_eq!(
+                result.map(|_| ()),
+                accepted,
+                "setup {} action {:?}",
+                index,
+                action
+            );
+            assert!(
+                !listed || accepted.is_ok(),
+                "setup {} action {:?}",
+                index,
+                action
+            );
+        }
+    }
+}
+
+// ========== Sub-second Accounting ==========
+
+#[test]
+fn test_pause_resume_cycles_keep_fractions_of_seconds() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+
+    for _ in 0..100 {
+        clock.advance(Duration::from_millis(1500));
+        service.pause().unwrap();
+        service.resume().unwrap();
+    }
+
+    // 100 stretches of 1.5 s are 150 s, nothing lost or added per pause
+    let state = service.get_state();
+    assert_eq!(
+        state.remaining_millis,
+        (WORK_DURATION_SECS as u64 - 150) * 1000
+    );
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 150);
+}
+
+#[test]
+fn test_remaining_secs_rounds_up_started_second() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+
+    clock.advance(Duration::from_millis(250));
+    let state = service.get_state();
+    assert_eq!(
+        state.remaining_millis,
+        WORK_DURATION_SECS as u64 * 1000 - 250
+    );
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+
+    // Zero only once the session is done
+    clock.advance(Duration::from_millis(
+        WORK_DURATION_SECS as u64 * 1000 - 251,
+    ));
+    let state = service.get_state();
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, 1);
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(service.get_state().status, Status::Complete);
+}
+
+#[test]
+fn test_adjust_keeps_fraction_of_current_second() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    clock.advance(Duration::from_millis(600_400));
+
+    let state = service.adjust_remaining(-60).unwrap();
+
+    assert_eq!(state.remaining_millis, 839_600);
+    assert_eq!(state.remaining_secs, 840);
+    assert_eq!(state.duration_secs, WORK_DURATION_SECS - 60);
+}
+
+#[test]
+fn test_restart_keeps_milliseconds() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    clock.advance(Duration::from_millis(1234));
+    service.pause().unwrap();
+
+    let mut restored = restart_after(&service, &clock, 600);
+
+    assert_eq!(
+        restored.get_state().remaining_millis,
+        WORK_DURATION_SECS as u64 * 1000 - 1234
+    );
+}
+
+// ========== System Suspend ==========
+
+fn suspend_service(on_suspend: SuspendPolicy) -> (TimerService, ManualClock) {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(TimerConfig {
+            on_suspend,
+            ..TimerConfig::default()
+        })
+        .unwrap();
+    (service, clock)
+}
+
+#[test]
+fn test_suspend_counts_toward_running_session_by_default() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    let asleep_at = clock.wall_now();
+
+    clock.sleep(Duration::from_secs(600));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 660);
+    service.clear().unwrap();
+    let records = service.take_finished_sessions();
+    assert_eq!(
+        records[0].suspensions,
+        vec![Suspension {
+            at: asleep_at,
+            secs: 600,
+            counted: true,
+        }]
+    );
+    assert_eq!(records[0].focused_secs, 660);
+}
+
+#[test]
+fn test_suspend_past_the_end_completes_with_overtime() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    clock.sleep(Duration::from_secs(WORK_DURATION_SECS as u64));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::Complete);
+    assert_eq!(state.overtime_secs, Some(60));
+}
+
+#[test]
+fn test_suspend_in_overtime_counts_toward_overtime() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 30);
+
+    clock.sleep(Duration::from_secs(300));
+
+    assert_eq!(service.get_state().overtime_secs, Some(330));
+}
+
+#[test]
+fn test_pause_policy_pauses_at_moment_of_suspend() {
+    let (mut service, clock) = suspend_service(SuspendPolicy::Pause);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    let asleep_at = clock.wall_now();
+
+    clock.sleep(Duration::from_secs(600));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+    assert_eq!(service.paused_at, Some(asleep_at));
+
+    fast_forward(&mut service, &clock, 10);
+    service.clear().unwrap();
+    let records = service.take_finished_sessions();
+    assert_eq!(records[0].focused_secs, 60);
+    assert_eq!(records[0].pause_count, 1);
+    assert_eq!(records[0].paused_secs, 610);
+    assert_eq!(
+        records[0].suspensions,
+        vec![Suspension {
+            at: asleep_at,
+            secs: 600,
+            counted: false,
+        }]
+    );
+}
+
+#[test]
+fn test_pause_policy_pauses_overtime() {
+    let (mut service, clock) = suspend_service(SuspendPolicy::Pause);
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 30);
+
+    clock.sleep(Duration::from_secs(300));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::OvertimePaused);
+    assert_eq!(state.overtime_secs, Some(30));
+}
+
+#[test]
+fn test_small_clock_drift_is_not_a_suspend() {
+    let (mut service, clock) = suspend_service(SuspendPolicy::Pause);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    clock.sleep(Duration::from_secs(2));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+    service.clear().unwrap();
+    assert!(service.take_finished_sessions()[0].suspensions.is_empty());
+}
+
+#[test]
+fn test_setting_the_clock_forward_is_not_a_suspend() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+
+    clock.set_forward(Duration::from_secs(3600));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::Running);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+    service.clear().unwrap();
+    assert!(service.take_finished_sessions()[0].suspensions.is_empty());
+}
+
+#[test]
+fn test_suspend_while_paused_changes_nothing() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.pause().unwrap();
+
+    clock.sleep(Duration::from_secs(600));
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::Paused);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS - 60);
+    service.clear().unwrap();
+    assert!(service.take_finished_sessions()[0].suspensions.is_empty());
+}
+
+// ========== Overtime Policy ==========
+
+fn overtime_service(overtime: OvertimePolicy) -> (TimerService, ManualClock) {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(TimerConfig {
+            overtime,
+            ..TimerConfig::default()
+        })
+        .unwrap();
+    (service, clock)
+}
+
+#[test]
+fn test_overtime_cap_is_configurable() {
+    let (mut service, clock) = overtime_service(OvertimePolicy::Cap { cap_secs: 600 });
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 900);
+
+    assert_eq!(service.get_state().overtime_secs, Some(600));
+    service.pause().unwrap();
+    assert_eq!(service.get_state().overtime_paused_secs, Some(600));
+}
+
+#[test]
+fn test_uncapped_overtime_counts_past_an_hour() {
+    let (mut service, clock) = overtime_service(OvertimePolicy::Uncapped);
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 7230);
+
+    assert_eq!(service.get_state().overtime_secs, Some(7230));
+    service.start().unwrap();
+    assert_eq!(service.take_finished_sessions()[0].overtime_secs, 7230);
+}
+
+#[test]
+fn test_auto_pause_stops_overtime_at_cap() {
+    let (mut service, clock) = overtime_service(OvertimePolicy::AutoPause { cap_secs: 600 });
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    let completed_at = clock.wall_now();
+    fast_forward(&mut service, &clock, 900);
+
+    let state = service.get_state();
+    assert_eq!(state.status, Status::OvertimePaused);
+    assert_eq!(state.overtime_paused_secs, Some(600));
+    assert_eq!(state.state_label, "Overtime paused (work)");
+    assert_eq!(
+        service.paused_at,
+        Some(completed_at + chrono::Duration::seconds(600))
+    );
+    // Resuming would only pause again
+    assert!(!state.available_actions.contains(&TimerAction::Resume));
+    assert!(state.available_actions.contains(&TimerAction::Start));
+}
+
+#[test]
+fn test_auto_clear_returns_to_ready_after_overtime() {
+    let (mut service, clock) = overtime_service(OvertimePolicy::AutoClear { after_secs: 300 });
+    service.set_phase(Phase::Break);
+    service.start().unwrap();
+    fast_forward(&mut service, &clock, 60);
+    service.set_phase(Phase::Work);
+    service.take_finished_sessions();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 299);
+    assert_eq!(service.get_state().status, Status::Complete);
+
+    fast_forward(&mut service, &clock, 100);
+    let state = service.get_state();
+
+    assert_eq!(state.status, Status::WorkReady);
+    assert_eq!(state.remaining_secs, WORK_DURATION_SECS);
+    assert_eq!(state.overtime_secs, None);
+    assert!(!state.completion_flag);
+    assert_eq!(state.completed_work_sessions, 1);
+    let records = service.take_finished_sessions();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].outcome, SessionOutcome::Completed);
+    assert_eq!(records[0].overtime_secs, 300);
+    // Time parked in the break is kept
+    assert_eq!(
+        service.paused_break,
+        Some(Duration::from_secs((BREAK_DURATION_SECS - 60).into()))
+    );
+}
+
+#[test]
+fn test_overtime_limit_is_validated() {
+    let mut service = TimerService::new();
+    let config = TimerConfig {
+        overtime: OvertimePolicy::AutoClear { after_secs: 10 },
+        ..TimerConfig::default()
+    };
+
+    assert_eq!(
+        service.set_config(config).unwrap_err(),
+        TimerError::InvalidConfig {
+            field: "overtime.afterSecs",
+            min: 60,
+            max: 86400,
+        }
+    );
+}
+
+#[test]
+fn test_overtime_policy_deserializes_with_kind() {
+    let config: TimerConfig =
+        serde_json::from_str(r#"{"overtime":{"kind":"autoPause","capSecs":1800}}"#).unwrap();
+
+    assert_eq!(
+        config.overtime,
+        OvertimePolicy::AutoPause { cap_secs: 1800 }
+    );
+    assert_eq!(
+        serde_json::from_str::<TimerConfig>("{}").unwrap().overtime,
+        OvertimePolicy::Cap { cap_secs: 3599 }
+    );
+}
+
+// ========== Overtime Reminders ==========
+
+// Helper: levels of the overtime reminders queued since the last call
+fn reminder_levels(service: &mut TimerService) -> Vec<u32> {
+    service
+        .take_events()
+        .into_iter()
+        .filter_map(|event| match event {
+            TimerEvent::OvertimeReminder { level, .. } => Some(level),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_overtime_reminders_escalate_each_interval() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 119);
+    assert!(reminder_levels(&mut service).is_empty());
+
+    fast_forward(&mut service, &clock, 1);
+    assert_eq!(
+        service.take_events(),
+        vec![TimerEvent::OvertimeReminder {
+            phase: Phase::Work,
+            level: 1,
+            overtime_secs: 120,
+        }]
+    );
+    fast_forward(&mut service, &clock, 120);
+    service.get_state();
+    assert_eq!(reminder_levels(&mut service), vec![2]);
+}
+
+#[test]
+fn test_missed_reminders_give_one_at_level_reached() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    service.take_events();
+
+    fast_forward(&mut service, &clock, 500);
+
+    assert_eq!(reminder_levels(&mut service), vec![4]);
+}
+
+#[test]
+fn test_no_overtime_reminders_while_overtime_paused() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 100);
+    service.pause().unwrap();
+
+    fast_forward(&mut service, &clock, 600);
+    assert!(reminder_levels(&mut service).is_empty());
+
+    // Overtime picks up where it paused
+    service.resume().unwrap();
+    fast_forward(&mut service, &clock, 20);
+    assert_eq!(reminder_levels(&mut service), vec![1]);
+}
+
+#[test]
+fn test_overtime_reminders_start_over_after_next_completion() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 240);
+    service.start().unwrap();
+    service.take_events();
+
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 120);
+
+    assert_eq!(reminder_levels(&mut service), vec![1]);
+}
+
+#[test]
+fn test_overtime_reminders_can_be_turned_off() {
+    let (mut service, clock) = new_service();
+    service
+        .set_config(TimerConfig {
+            overtime_reminder_secs: None,
+            ..TimerConfig::default()
+        })
+        .unwrap();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+
+    fast_forward(&mut service, &clock, 600);
+
+    assert!(reminder_levels(&mut service).is_empty());
+}
+
+#[test]
+fn test_undo_does_not_repeat_overtime_reminder() {
+    let (mut service, clock) = new_service();
+    service.start().unwrap();
+    complete_session(&mut service, &clock);
+    fast_forward(&mut service, &clock, 130);
+    service.take_events();
+
+    service.undoable(TimerService::pause).unwrap();
+    service.undo().unwrap();
+    service.get_state();
+
+    assert!(reminder_levels(&mut service).is_empty());
+}
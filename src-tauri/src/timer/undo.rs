@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+
+use super::{SessionRecord, TimerError, TimerService, TimerSnapshot, TimerState};
+
+// Actions kept for undo; older ones are forgotten
+pub(crate) const UNDO_LIMIT: usize = 20;
+
+/// State from before an undoable action, and the sessions the action ended.
+pub(crate) struct UndoEntry {
+    snapshot: TimerSnapshot,
+    finished: Vec<SessionRecord>,
+}
+
+pub(crate) type UndoStack = VecDeque<UndoEntry>;
+
+impl TimerService {
+    /// Run a timer action, keeping the state from before it so `undo` can go
+    /// back to it. Nothing is kept if the action fails.
+    pub fn undoable<T>(
+        &mut self,
+        action: impl FnOnce(&mut Self) -> Result<T, TimerError>,
+    ) -> Result<T, TimerError> {
+        // Completions that are already due belong to the state before
+        self.update_remaining();
+        let snapshot = self.snapshot();
+        let finished_before = self.finished_sessions.len();
+
+        let result = action(self)?;
+
+        let finished = self.finished_sessions[finished_before..].to_vec();
+        self.undo_stack.push_back(UndoEntry { snapshot, finished });
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.pop_front();
+        }
+        Ok(result)
+    }
+
+    /// Go back to the state before the last undoable action. Real time keeps
+    /// counting: a session that was running is as far along as if the action
+    /// never happened. Sessions the action ended are withdrawn from the
+    /// history; see `take_undone_sessions`. The configuration and program
+    /// stay as they are now.
+    pub fn undo(&mut self) -> Result<TimerState, TimerError> {
+        let entry = self
+            .undo_stack
+            .pop_back()
+            .ok_or(TimerError::NothingToUndo)?;
+
+        for record in entry.finished {
+            // Not yet handed out, so it can simply be dropped
+            match self.finished_sessions.iter().rposition(|r| *r == record) {
+                Some(index) => {
+                    self.finished_sessions.remove(index);
+                }
+                None => self.undone_sessions.push(record),
+            }
+        }
+
+        let mut snapshot = entry.snapshot;
+        let same_program = snapshot.program == self.program;
+        snapshot.config = self.config;
+        snapshot.program = self.program.clone();
+        self.load_snapshot(snapshot);
+        // A step of another program means nothing in this one; pick it as
+        // `set_program` does
+        if !same_program {
+            self.step = self.first_step_in_phase();
+        }
+        // As with `set_config`, a fresh Ready state uses the current durations
+        if self.status == self.phase.ready_status() {
+            self.load_duration();
+        }
+        Ok(self.get_state())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Drain the sessions withdrawn by `undo` that were already taken with
+    /// `take_finished_sessions`, so they can be removed from the history.
+    pub fn take_undone_sessions(&mut self) -> Vec<SessionRecord> {
+        std::mem::take(&mut self.undone_sessions)
+    }
+}
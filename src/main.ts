@@ -1,52 +1,200 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+type Phase = 'work' | 'break' | 'longBreak';
+
+type TimerAction =
+  | 'start'
+  | 'pause'
+  | 'resume'
+  | 'stopWork'
+  | 'extend'
+  | 'shorten'
+  | 'skip'
+  | 'clear'
+  | 'undo'
+  | 'recordInterruption'
+  | 'setStep';
+
+interface ProgramStep {
+  phase: Phase;
+  durationSecs: number;
+  name?: string;
+}
 
 interface TimerState {
-  phase: 'work' | 'break';
-  status: 'workReady' | 'breakReady' | 'running' | 'paused' | 'complete' | 'overtimePaused';
+  phase: Phase;
+  status: 'workReady' | 'breakReady' | 'longBreakReady' | 'running' | 'paused' | 'complete' | 'overtimePaused';
   remainingSecs: number;
+  remainingMillis: number;
   durationSecs: number;
+  countingUp: boolean;
+  elapsedSecs?: number;
   completionFlag: boolean;
+  startedAt?: string;
+  pausedAt?: string;
+  endsAt?: string;
   stateLabel: string;
   overtimeSecs?: number;
   overtimePausedSecs?: number;
+  completedWorkSessions: number;
+  completedToday: number;
+  dailyGoal: number;
+  nextPhase: Phase;
+  stepIndex?: number;
+  stepName?: string;
+  nextStep?: ProgramStep;
+  task?: string;
+  project?: string;
+  internalInterruptions: number;
+  externalInterruptions: number;
+  canUndo: boolean;
+  availableActions: TimerAction[];
+  destructiveActions: TimerAction[];
+}
+
+interface TimerCompletedEvent {
+  kind: 'completed';
+  phase: Phase;
+  completedAt: string;
+}
+
+interface TimerOvertimeReminderEvent {
+  kind: 'overtimeReminder';
+  phase: Phase;
+  level: number;
+  overtimeSecs: number;
 }
 
+type InterruptionKind = 'internal' | 'external';
+
+type ExportFormat = 'csv' | 'jsonl' | 'ics';
+
+// Actions the backend may flag as destructive, which are confirmed first
+type ConfirmedAction = 'clear' | 'skip';
+
 const CHIME_DURATION_SEC = 3.0;
+const REMINDER_BEEP_SEC = 0.4;
+const MAX_REMINDER_BEEPS = 4;
+const ADJUST_STEP_SECS = 5 * 60;
+
+const CONFIRMATIONS: Record<ConfirmedAction, { command: string; message: string; label: string }> = {
+  clear: {
+    command: 'clear_timer',
+    message: 'This will remove the current time and status. Continue?',
+    label: 'Clear',
+  },
+  skip: {
+    command: 'skip',
+    message: 'This will drop the time left and move on to the next phase. Continue?',
+    label: 'Skip',
+  },
+};
 
-let pollInterval: number | null = null;
-let lastCompletionFlag = false;
 let audioContext: AudioContext | null = null;
 
 let startBtn: HTMLButtonElement;
 let pauseBtn: HTMLButtonElement;
 let resumeBtn: HTMLButtonElement;
+let stopWorkBtn: HTMLButtonElement;
+let extendBtn: HTMLButtonElement;
+let shortenBtn: HTMLButtonElement;
 let clearBtn: HTMLButtonElement;
+let skipBtn: HTMLButtonElement;
+let undoBtn: HTMLButtonElement;
+let internalBtn: HTMLButtonElement;
+let externalBtn: HTMLButtonElement;
+let interruptionCount: HTMLSpanElement;
 let workBtn: HTMLButtonElement;
 let breakBtn: HTMLButtonElement;
+let longBreakBtn: HTMLButtonElement;
 let timerDisplay: HTMLDivElement;
 let stateLabel: HTMLDivElement;
+let taskLabel: HTMLDivElement;
+let taskInput: HTMLInputElement;
+let projectInput: HTMLInputElement;
+let endsAtLabel: HTMLDivElement;
+let goalProgress: HTMLDivElement;
+let stepLabel: HTMLDivElement;
 let confirmDialog: HTMLDivElement;
-let confirmClearBtn: HTMLButtonElement;
-let cancelClearBtn: HTMLButtonElement;
-
-function shouldConfirmClear(state: TimerState): boolean {
-  // Confirm if timer is running or paused, OR if in Ready state with paused time
-  const hasActivity = state.status === 'running' || state.status === 'paused';
-  const hasPausedTime = (state.status === 'workReady' || state.status === 'breakReady')
-    && state.remainingSecs !== state.durationSecs;
-  return hasActivity || hasPausedTime;
+let confirmMessage: HTMLParagraphElement;
+let confirmActionBtn: HTMLButtonElement;
+let cancelConfirmBtn: HTMLButtonElement;
+let pendingAction: ConfirmedAction | null = null;
+let exportDialog: HTMLDivElement;
+let exportFromInput: HTMLInputElement;
+let exportToInput: HTMLInputElement;
+let exportFormatSelect: HTMLSelectElement;
+let exportStatus: HTMLDivElement;
+let confirmExportBtn: HTMLButtonElement;
+let cancelExportBtn: HTMLButtonElement;
+
+function canDo(state: TimerState, action: TimerAction): boolean {
+  return state.availableActions.includes(action);
 }
 
-function showClearConfirmDialog() {
+function showConfirmDialog(action: ConfirmedAction) {
+  pendingAction = action;
+  confirmMessage.textContent = CONFIRMATIONS[action].message;
+  confirmActionBtn.textContent = CONFIRMATIONS[action].label;
   confirmDialog.style.display = 'flex';
-  confirmClearBtn.focus();
+  confirmActionBtn.focus();
 }
 
-function hideClearConfirmDialog() {
+function hideConfirmDialog() {
+  pendingAction = null;
   confirmDialog.style.display = 'none';
 }
 
+// Run a clear or skip, asking first if it would throw away unfinished time
+async function runConfirmed(action: ConfirmedAction) {
+  try {
+    const state = await invoke<TimerState>('get_state');
+
+    if (state.destructiveActions.includes(action)) {
+      showConfirmDialog(action);
+    } else {
+      // Nothing unfinished to lose, so go ahead without confirmation
+      await invoke(CONFIRMATIONS[action].command);
+      await updateUI();
+    }
+  } catch (error) {
+    console.error(`Failed to ${action}:`, error);
+  }
+}
+
+// Local date as YYYY-MM-DD, the format of date inputs and the backend
+function formatDateInput(date: Date): string {
+  const month = (date.getMonth() + 1).toString().padStart(2, '0');
+  const day = date.getDate().toString().padStart(2, '0');
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+function showExportDialog() {
+  // Default to the current month so far
+  const today = new Date();
+  exportFromInput.value = formatDateInput(new Date(today.getFullYear(), today.getMonth(), 1));
+  exportToInput.value = formatDateInput(today);
+  exportStatus.textContent = '';
+  exportDialog.style.display = 'flex';
+  confirmExportBtn.focus();
+}
+
+function hideExportDialog() {
+  exportDialog.style.display = 'none';
+}
+
 function playCompletionChime() {
+  playChime(1, CHIME_DURATION_SEC);
+}
+
+// One short beep more with each reminder, so a completion that keeps being
+// missed gets harder to ignore
+function playOvertimeReminder(level: number) {
+  playChime(Math.min(level, MAX_REMINDER_BEEPS), REMINDER_BEEP_SEC);
+}
+
+function playChime(count: number, durationSec: number) {
   try {
     // Create AudioContext on first use (required for user gesture in some browsers)
     if (!audioContext) {
@@ -58,75 +206,146 @@ function playCompletionChime() {
       audioContext.resume();
     }
 
-    const oscillator = audioContext.createOscillator();
-    const gainNode = audioContext.createGain();
+    for (let i = 0; i < count; i++) {
+      // Tones are spaced half their length apart
+      const start = audioContext.currentTime + i * durationSec * 1.5;
+      const oscillator = audioContext.createOscillator();
+      const gainNode = audioContext.createGain();
 
-    oscillator.connect(gainNode);
-    gainNode.connect(audioContext.destination);
+      oscillator.connect(gainNode);
+      gainNode.connect(audioContext.destination);
 
-    // Pleasant chime: 880Hz (A5)
-    oscillator.frequency.value = 880;
-    oscillator.type = 'sine';
+      // Pleasant chime: 880Hz (A5)
+      oscillator.frequency.value = 880;
+      oscillator.type = 'sine';
 
-    // Fade out to avoid click
-    gainNode.gain.setValueAtTime(0.3, audioContext.currentTime);
-    gainNode.gain.exponentialRampToValueAtTime(0.01, audioContext.currentTime + CHIME_DURATION_SEC);
+      // Fade out to avoid click
+      gainNode.gain.setValueAtTime(0.3, start);
+      gainNode.gain.exponentialRampToValueAtTime(0.01, start + durationSec);
 
-    oscillator.start(audioContext.currentTime);
-    oscillator.stop(audioContext.currentTime + CHIME_DURATION_SEC);
+      oscillator.start(start);
+      oscillator.stop(start + durationSec);
+    }
   } catch (err) {
     console.warn('Could not play chime:', err);
   }
 }
 
+function formatClockTime(timestamp: string): string {
+  return new Date(timestamp).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+}
+
+const PHASE_NAMES: Record<Phase, string> = {
+  work: 'Work',
+  break: 'Break',
+  longBreak: 'Long break',
+};
+
+// "Step 1 · Warm-up · next: Work 50 min", or empty without a program
+function formatStep(state: TimerState): string {
+  if (state.stepIndex === undefined) {
+    return '';
+  }
+  const parts = [`Step ${state.stepIndex + 1}`];
+  if (state.stepName) {
+    parts.push(state.stepName);
+  }
+  if (state.nextStep) {
+    const next = state.nextStep.name ?? PHASE_NAMES[state.nextStep.phase];
+    parts.push(`next: ${next} ${Math.round(state.nextStep.durationSecs / 60)} min`);
+  }
+  return parts.join(' · ');
+}
+
+// mm:ss, or h:mm:ss from an hour on (uncapped overtime can run that long)
 function formatTime(seconds: number): string {
-  const mins = Math.floor(seconds / 60);
+  const hours = Math.floor(seconds / 3600);
+  const mins = Math.floor((seconds % 3600) / 60);
   const secs = seconds % 60;
-  return `${mins.toString().padStart(2, '0')}:${secs.toString().padStart(2, '0')}`;
+  const minSec = `${mins.toString().padStart(2, '0')}:${secs.toString().padStart(2, '0')}`;
+  return hours > 0 ? `${hours}:${minSec}` : minSec;
 }
 
-async function updateUI() {
-  try {
-    const state = await invoke<TimerState>('get_state');
+function render(state: TimerState) {
+  // Handle overtime display
+  if (state.overtimePausedSecs !== undefined) {
+    timerDisplay.textContent = `-${formatTime(state.overtimePausedSecs)}`;
+    timerDisplay.classList.add('overtime');
+  } else if (state.overtimeSecs !== undefined) {
+    timerDisplay.textContent = `-${formatTime(state.overtimeSecs)}`;
+    timerDisplay.classList.add('overtime');
+  } else {
+    // Flowtime work counts up from zero
+    timerDisplay.textContent = formatTime(state.elapsedSecs ?? state.remainingSecs);
+    timerDisplay.classList.remove('overtime');
+  }
 
-    // Handle overtime display
-    if (state.overtimePausedSecs !== undefined) {
-      timerDisplay.textContent = `-${formatTime(state.overtimePausedSecs)}`;
-      timerDisplay.classList.add('overtime');
-    } else if (state.overtimeSecs !== undefined) {
-      timerDisplay.textContent = `-${formatTime(state.overtimeSecs)}`;
-      timerDisplay.classList.add('overtime');
-    } else {
-      timerDisplay.textContent = formatTime(state.remainingSecs);
-      timerDisplay.classList.remove('overtime');
-    }
+  stateLabel.textContent = state.stateLabel;
+  taskLabel.textContent = [state.task, state.project].filter(Boolean).join(' · ');
+  stepLabel.textContent = formatStep(state);
+  endsAtLabel.textContent = state.endsAt ? `Finishes at ${formatClockTime(state.endsAt)}` : '';
+  const goalReached = state.completedToday >= state.dailyGoal;
+  goalProgress.textContent = `${state.completedToday} / ${state.dailyGoal} today${goalReached ? ' · goal reached' : ''}`;
+  goalProgress.classList.toggle('reached', goalReached);
+
+  // Sync active mode button with backend phase, and hint the phase that
+  // should follow a completed session
+  const modeButtons: [Phase, HTMLButtonElement][] = [
+    ['work', workBtn],
+    ['break', breakBtn],
+    ['longBreak', longBreakBtn],
+  ];
+  for (const [phase, button] of modeButtons) {
+    button.classList.toggle('active', state.phase === phase);
+    button.classList.toggle('suggested', state.status === 'complete' && state.nextPhase === phase);
+  }
 
-    stateLabel.textContent = state.stateLabel;
+  // The backend lists the actions the current state allows
+  startBtn.disabled = !canDo(state, 'start');
+  pauseBtn.disabled = !canDo(state, 'pause');
+  resumeBtn.disabled = !canDo(state, 'resume');
+  extendBtn.disabled = !canDo(state, 'extend');
+  shortenBtn.disabled = !canDo(state, 'shorten');
+  stopWorkBtn.hidden = !state.countingUp;
+  stopWorkBtn.disabled = !canDo(state, 'stopWork');
+  skipBtn.disabled = !canDo(state, 'skip');
+  clearBtn.disabled = !canDo(state, 'clear');
+  undoBtn.disabled = !canDo(state, 'undo');
+  internalBtn.disabled = !canDo(state, 'recordInterruption');
+  externalBtn.disabled = !canDo(state, 'recordInterruption');
+  const interruptions = state.internalInterruptions + state.externalInterruptions;
+  interruptionCount.textContent = interruptions > 0
+    ? `${state.internalInterruptions} internal · ${state.externalInterruptions} external`
+    : '';
+}
 
-    // Sync active mode button with backend phase
-    if (state.phase === 'work') {
-      workBtn.classList.add('active');
-      breakBtn.classList.remove('active');
-    } else {
-      breakBtn.classList.add('active');
-      workBtn.classList.remove('active');
-    }
+async function adjustRemaining(deltaSecs: number) {
+  try {
+    render(await invoke<TimerState>('adjust_remaining', { deltaSecs }));
+  } catch (error) {
+    console.error('Failed to adjust timer:', error);
+  }
+}
 
-    // Update button states - Start enabled when in Ready states
-    startBtn.disabled = !(state.status === 'workReady' || state.status === 'breakReady');
-    pauseBtn.disabled = !(state.status === 'running' || state.status === 'complete');
-    resumeBtn.disabled = !(state.status === 'paused' || state.status === 'overtimePaused');
-    // Clear enabled unless in fresh Ready state (remaining time = full duration)
-    const isFreshState = ((state.status === 'workReady' || state.status === 'breakReady')
-      && state.remainingSecs === state.durationSecs);
-    clearBtn.disabled = isFreshState;
-
-    // Detect completion transitions and play chime
-    if (state.completionFlag && !lastCompletionFlag) {
-      playCompletionChime();
-    }
+async function undoLastAction() {
+  try {
+    render(await invoke<TimerState>('undo'));
+  } catch (error) {
+    console.error('Failed to undo:', error);
+  }
+}
 
-    lastCompletionFlag = state.completionFlag;
+async function recordInterruption(kind: InterruptionKind) {
+  try {
+    render(await invoke<TimerState>('record_interruption', { kind, note: null }));
+  } catch (error) {
+    console.error('Failed to record interruption:', error);
+  }
+}
+
+async function updateUI() {
+  try {
+    render(await invoke<TimerState>('get_state'));
   } catch (error) {
     console.error('Failed to get state:', error);
   }
@@ -135,9 +354,12 @@ async function updateUI() {
 function attachEventListeners() {
   startBtn.addEventListener('click', async () => {
     try {
-      await invoke('start_timer');
+      // Blank inputs keep the task the phase was last started with
+      await invoke('start_timer', {
+        task: taskInput.value.trim() || null,
+        project: projectInput.value.trim() || null,
+      });
       await updateUI();
-      startPolling();
     } catch (error) {
       console.error('Failed to start timer:', error);
     }
@@ -161,41 +383,48 @@ function attachEventListeners() {
     }
   });
 
-  clearBtn.addEventListener('click', async () => {
+  skipBtn.addEventListener('click', () => runConfirmed('skip'));
+
+  undoBtn.addEventListener('click', () => undoLastAction());
+
+  extendBtn.addEventListener('click', () => adjustRemaining(ADJUST_STEP_SECS));
+  shortenBtn.addEventListener('click', () => adjustRemaining(-ADJUST_STEP_SECS));
+
+  stopWorkBtn.addEventListener('click', async () => {
     try {
-      const state = await invoke<TimerState>('get_state');
-
-      if (shouldConfirmClear(state)) {
-        showClearConfirmDialog();
-      } else {
-        // Directly clear without confirmation for Ready states
-        await invoke('clear_timer');
-        await updateUI();
-        stopPolling();
-      }
+      await invoke('stop_work');
+      await updateUI();
     } catch (error) {
-      console.error('Failed to get state:', error);
+      console.error('Failed to stop work:', error);
     }
   });
 
-  confirmClearBtn.addEventListener('click', async () => {
+  clearBtn.addEventListener('click', () => runConfirmed('clear'));
+
+  confirmActionBtn.addEventListener('click', async () => {
+    if (!pendingAction) {
+      return;
+    }
+    const action = pendingAction;
     try {
-      await invoke('clear_timer');
-      hideClearConfirmDialog();
+      await invoke(CONFIRMATIONS[action].command);
+      hideConfirmDialog();
       await updateUI();
-      stopPolling();
     } catch (error) {
-      console.error('Failed to clear timer:', error);
+      console.error(`Failed to ${action}:`, error);
     }
   });
 
-  cancelClearBtn.addEventListener('click', () => {
-    hideClearConfirmDialog();
+  internalBtn.addEventListener('click', () => recordInterruption('internal'));
+  externalBtn.addEventListener('click', () => recordInterruption('external'));
+
+  cancelConfirmBtn.addEventListener('click', () => {
+    hideConfirmDialog();
   });
 
   confirmDialog.addEventListener('click', (event) => {
     if (event.target === confirmDialog) {
-      hideClearConfirmDialog();
+      hideConfirmDialog();
     }
   });
 
@@ -217,24 +446,67 @@ function attachEventListeners() {
     }
   });
 
+  longBreakBtn.addEventListener('click', async () => {
+    try {
+      await invoke('set_phase', { phase: 'longBreak' });
+      await updateUI();
+    } catch (error) {
+      console.error('Failed to set long break phase:', error);
+    }
+  });
+
+  confirmExportBtn.addEventListener('click', async () => {
+    try {
+      const path = await invoke<string>('export_history', {
+        query: {
+          from: exportFromInput.value,
+          to: exportToInput.value,
+          format: exportFormatSelect.value as ExportFormat,
+        },
+      });
+      exportStatus.textContent = `Saved to ${path}`;
+    } catch (error) {
+      console.error('Failed to export history:', error);
+      exportStatus.textContent = 'Export failed';
+    }
+  });
+
+  cancelExportBtn.addEventListener('click', () => {
+    hideExportDialog();
+  });
+
+  exportDialog.addEventListener('click', (event) => {
+    if (event.target === exportDialog) {
+      hideExportDialog();
+    }
+  });
+
   document.addEventListener('keydown', (event) => {
     if (event.key === 'Escape' && confirmDialog.style.display === 'flex') {
-      hideClearConfirmDialog();
+      hideConfirmDialog();
+    }
+    if (event.key === 'Escape' && exportDialog.style.display === 'flex') {
+      hideExportDialog();
+    }
+    // Ctrl+Z / Cmd+Z, unless typing in a field that has its own undo
+    const typing = event.target instanceof HTMLInputElement;
+    if (event.key === 'z' && (event.ctrlKey || event.metaKey) && !typing && !undoBtn.disabled) {
+      event.preventDefault();
+      undoLastAction();
     }
   });
 }
 
-function startPolling() {
-  if (pollInterval === null) {
-    pollInterval = window.setInterval(updateUI, 1000); // 1 Hz polling
-  }
-}
-
-function stopPolling() {
-  if (pollInterval !== null) {
-    clearInterval(pollInterval);
-    pollInterval = null;
-  }
+// The backend ticker pushes every state change and completion, so the
+// countdown and chime keep working while the window is hidden or throttled
+async function listenToTimer() {
+  await listen<TimerState>('timer-state', (event) => render(event.payload));
+  await listen<TimerCompletedEvent>('timer-completed', () => playCompletionChime());
+  await listen<TimerOvertimeReminderEvent>('timer-overtime-reminder', (event) =>
+    playOvertimeReminder(event.payload.level),
+  );
+  // Sent by the History > Export menu item
+  await listen('export-history-requested', () => showExportDialog());
 }
 
 // Wait for DOM to be ready before initializing
@@ -243,19 +515,43 @@ document.addEventListener('DOMContentLoaded', () => {
   startBtn = document.getElementById('start-btn') as HTMLButtonElement;
   pauseBtn = document.getElementById('pause-btn') as HTMLButtonElement;
   resumeBtn = document.getElementById('resume-btn') as HTMLButtonElement;
+  stopWorkBtn = document.getElementById('stop-work-btn') as HTMLButtonElement;
+  extendBtn = document.getElementById('extend-btn') as HTMLButtonElement;
+  shortenBtn = document.getElementById('shorten-btn') as HTMLButtonElement;
   clearBtn = document.getElementById('clear-btn') as HTMLButtonElement;
+  skipBtn = document.getElementById('skip-btn') as HTMLButtonElement;
+  undoBtn = document.getElementById('undo-btn') as HTMLButtonElement;
+  internalBtn = document.getElementById('internal-btn') as HTMLButtonElement;
+  externalBtn = document.getElementById('external-btn') as HTMLButtonElement;
+  interruptionCount = document.getElementById('interruption-count') as HTMLSpanElement;
   workBtn = document.getElementById('work-btn') as HTMLButtonElement;
   breakBtn = document.getElementById('break-btn') as HTMLButtonElement;
+  longBreakBtn = document.getElementById('long-break-btn') as HTMLButtonElement;
   timerDisplay = document.getElementById('timer-display') as HTMLDivElement;
   stateLabel = document.getElementById('state-label') as HTMLDivElement;
-  confirmDialog = document.getElementById('clear-confirm-dialog') as HTMLDivElement;
-  confirmClearBtn = document.getElementById('confirm-clear-btn') as HTMLButtonElement;
-  cancelClearBtn = document.getElementById('cancel-clear-btn') as HTMLButtonElement;
+  taskLabel = document.getElementById('task-label') as HTMLDivElement;
+  taskInput = document.getElementById('task-input') as HTMLInputElement;
+  projectInput = document.getElementById('project-input') as HTMLInputElement;
+  endsAtLabel = document.getElementById('ends-at') as HTMLDivElement;
+  goalProgress = document.getElementById('goal-progress') as HTMLDivElement;
+  stepLabel = document.getElementById('step-label') as HTMLDivElement;
+  confirmDialog = document.getElementById('confirm-dialog') as HTMLDivElement;
+  confirmMessage = document.getElementById('confirm-dialog-title') as HTMLParagraphElement;
+  confirmActionBtn = document.getElementById('confirm-action-btn') as HTMLButtonElement;
+  cancelConfirmBtn = document.getElementById('cancel-confirm-btn') as HTMLButtonElement;
+  exportDialog = document.getElementById('export-dialog') as HTMLDivElement;
+  exportFromInput = document.getElementById('export-from') as HTMLInputElement;
+  exportToInput = document.getElementById('export-to') as HTMLInputElement;
+  exportFormatSelect = document.getElementById('export-format') as HTMLSelectElement;
+  exportStatus = document.getElementById('export-status') as HTMLDivElement;
+  confirmExportBtn = document.getElementById('confirm-export-btn') as HTMLButtonElement;
+  cancelExportBtn = document.getElementById('cancel-export-btn') as HTMLButtonElement;
 
   // Attach event listeners
   attachEventListeners();
 
   // Initialize UI
+  listenToTimer().catch((error) => console.error('Failed to listen to timer events:', error));
   updateUI();
 
   console.log('Pomodoro Timer initialized');